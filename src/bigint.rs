@@ -0,0 +1,533 @@
+//! Arbitrary-precision integers, for coefficients that would overflow `i64`
+
+use crate::{
+    ring::{ExactDiv, Ring, Saturating},
+    Gcd, Monomial, ParseError, Rational,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+/// Magnitudes are little-endian base `2^32` digits with no leading zeros
+type Limbs = Vec<u32>;
+
+fn trim(mut a: Limbs) -> Limbs {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+
+fn cmp(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add(a: &[u32], b: &[u32]) -> Limbs {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let sum = u64::from(x) + u64::from(*b.get(i).unwrap_or(&0)) + carry;
+        out.push(sum as u32);
+        carry = sum >> 32;
+    }
+    out.push(carry as u32);
+    trim(out)
+}
+
+/// `a - b` where `a >= b`
+fn sub(a: &[u32], b: &[u32]) -> Limbs {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let diff = i64::from(x) - i64::from(*b.get(i).unwrap_or(&0)) - borrow;
+        out.push(diff.rem_euclid(1 << 32) as u32);
+        borrow = i64::from(diff < 0);
+    }
+    trim(out)
+}
+
+fn mul(a: &[u32], b: &[u32]) -> Limbs {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut out = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let product = u64::from(x) * u64::from(y) + u64::from(out[i + j]) + carry;
+            out[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+    trim(out)
+}
+
+/// Quotient and remainder of dividing by a single nonzero digit
+fn div_rem_digit(a: &[u32], b: u32) -> (Limbs, u32) {
+    let mut out = vec![0; a.len()];
+    let mut rem = 0u64;
+    for (i, &x) in a.iter().enumerate().rev() {
+        let cur = rem << 32 | u64::from(x);
+        out[i] = (cur / u64::from(b)) as u32;
+        rem = cur % u64::from(b);
+    }
+    (trim(out), rem as u32)
+}
+
+/// Quotient and remainder of dividing by a nonzero `b`, by binary long division
+fn div_rem(a: &[u32], b: &[u32]) -> (Limbs, Limbs) {
+    if let [b] = *b {
+        let (quot, rem) = div_rem_digit(a, b);
+        return (quot, trim(vec![rem]));
+    }
+    if cmp(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+
+    let mut quot = vec![0; a.len()];
+    let mut rem = Vec::with_capacity(b.len() + 1);
+    for i in (0..a.len() * 32).rev() {
+        // rem = 2 * rem + bit i of a
+        let mut carry = (a[i / 32] >> (i % 32)) & 1;
+        for digit in rem.iter_mut() {
+            let next = *digit >> 31;
+            *digit = *digit << 1 | carry;
+            carry = next;
+        }
+        if carry != 0 {
+            rem.push(carry);
+        }
+
+        if cmp(&rem, b) != Ordering::Less {
+            rem = sub(&rem, b);
+            quot[i / 32] |= 1 << (i % 32);
+        }
+    }
+    (trim(quot), rem)
+}
+
+/// An integer of any size, stored as a sign and magnitude
+/// ```
+/// use embedded_algebra::{BigInt, Polynomial};
+///
+/// let poly: Polynomial<BigInt> = "4000000000a^2 + 3000000000".parse().unwrap();
+/// let cube = &(&poly * &poly) * &poly;
+///
+/// assert_eq!(
+///     cube.to_string(),
+///     "64000000000000000000000000000a^6 + 144000000000000000000000000000a^4 \
+///      + 108000000000000000000000000000a^2 + 27000000000000000000000000000"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Limbs,
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, magnitude: Limbs) -> Self {
+        let magnitude = trim(magnitude);
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    /// The absolute value
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.magnitude.clone())
+    }
+
+    /// Raise this to the power `exp` by repeated squaring
+    /// ```
+    /// use embedded_algebra::BigInt;
+    ///
+    /// assert_eq!(BigInt::from(-2).pow(100).to_string(), "1267650600228229401496703205376");
+    /// ```
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut acc = Self::from(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc * base.clone();
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.clone() * base;
+            }
+        }
+        acc
+    }
+
+    /// The quotient rounded towards zero and the remainder with the sign of this,
+    /// or `None` when dividing by zero
+    /// ```
+    /// use embedded_algebra::BigInt;
+    ///
+    /// let (quot, rem) = BigInt::from(-7).checked_div_rem(&BigInt::from(2)).unwrap();
+    /// assert_eq!((quot, rem), (BigInt::from(-3), BigInt::from(-1)));
+    /// ```
+    pub fn checked_div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        if rhs.magnitude.is_empty() {
+            return None;
+        }
+        let (quot, rem) = div_rem(&self.magnitude, &rhs.magnitude);
+        Some((
+            Self::from_parts(self.negative != rhs.negative, quot),
+            Self::from_parts(self.negative, rem),
+        ))
+    }
+}
+
+impl Ring for BigInt {
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn one() -> Self {
+        Self::from(1)
+    }
+
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Self::from(n))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        i128::try_from(self).ok()
+    }
+}
+
+/// Arithmetic never overflows
+impl Saturating for BigInt {}
+
+/// Arithmetic never overflows
+impl Saturating for Rational<BigInt> {}
+
+impl ExactDiv for BigInt {
+    fn exact_div(&self, rhs: &Self) -> Option<Self> {
+        match self.checked_div_rem(rhs)? {
+            (quot, rem) if rem.is_zero() => Some(quot),
+            _ => None,
+        }
+    }
+}
+
+impl Gcd for BigInt {
+    type Output = Self;
+
+    /// The non-negative greatest common divisor
+    fn gcd(&self, rhs: &Self) -> Self::Output {
+        let (mut m, mut n) = (self.magnitude.clone(), rhs.magnitude.clone());
+        while !m.is_empty() {
+            let rem = div_rem(&n, &m).1;
+            n = m;
+            m = rem;
+        }
+        Self::from_parts(false, n)
+    }
+}
+
+impl Add for BigInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.negative == rhs.negative {
+            return Self::from_parts(self.negative, add(&self.magnitude, &rhs.magnitude));
+        }
+        match cmp(&self.magnitude, &rhs.magnitude) {
+            Ordering::Less => Self::from_parts(rhs.negative, sub(&rhs.magnitude, &self.magnitude)),
+            _ => Self::from_parts(self.negative, sub(&self.magnitude, &rhs.magnitude)),
+        }
+    }
+}
+
+impl Sub for BigInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for BigInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_parts(
+            self.negative != rhs.negative,
+            mul(&self.magnitude, &rhs.magnitude),
+        )
+    }
+}
+
+impl Neg for BigInt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp(&self.magnitude, &other.magnitude),
+            (true, true) => cmp(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(n: $t) -> Self {
+                    let mut magnitude = i128::from(n).unsigned_abs();
+                    let mut limbs = Vec::new();
+                    while magnitude > 0 {
+                        limbs.push(magnitude as u32);
+                        magnitude >>= 32;
+                    }
+                    Self::from_parts(n < 0, limbs)
+                }
+            }
+
+            impl TryFrom<&BigInt> for $t {
+                type Error = crate::OverflowError;
+
+                fn try_from(n: &BigInt) -> Result<Self, Self::Error> {
+                    if n.magnitude.len() > 4 {
+                        return Err(crate::OverflowError::Coefficient);
+                    }
+                    let magnitude = n
+                        .magnitude
+                        .iter()
+                        .rev()
+                        .fold(0u128, |acc, &limb| acc << 32 | u128::from(limb));
+                    let value = if n.negative {
+                        0i128.checked_sub_unsigned(magnitude)
+                    } else {
+                        i128::try_from(magnitude).ok()
+                    };
+                    value
+                        .and_then(|value| <$t>::try_from(value).ok())
+                        .ok_or(crate::OverflowError::Coefficient)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, i128);
+
+impl<const N: usize> From<BigInt> for Monomial<BigInt, N> {
+    fn from(coefficient: BigInt) -> Self {
+        Self::coefficient(coefficient)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const BASE: u32 = 1_000_000_000;
+
+        // Split into base 10^9 digits, least significant first
+        let mut digits = Vec::new();
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            let (quot, rem) = div_rem_digit(&rest, BASE);
+            digits.push(rem);
+            rest = quot;
+        }
+
+        if self.negative {
+            f.write_str("-")?;
+        }
+        match digits.split_last() {
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                for digit in rest.iter().rev() {
+                    write!(f, "{:09}", digit)?;
+                }
+                Ok(())
+            }
+            None => f.write_str("0"),
+        }
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseError;
+
+    /// Parse decimal digits with an optional leading minus sign
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut magnitude = Vec::new();
+        for c in digits.chars() {
+            let digit = c.to_digit(10).ok_or(ParseError::Symbol)?;
+            magnitude = add(&mul(&magnitude, &[10]), &[digit]);
+        }
+        Ok(Self::from_parts(negative, magnitude))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BigInt;
+    use crate::{
+        groebner::Basis, order::Lex, ring::ExactDiv, Gcd, Monomial, OverflowError, ParseError,
+        Polynomial, Rational,
+    };
+    use core::{convert::TryFrom, iter};
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn round_trips_through_strings() {
+        for s in ["0", "-1", "4294967296", "-1000000000000000000000000000001"].iter() {
+            assert_eq!(big(s).to_string(), *s);
+        }
+        assert_eq!(i64::try_from(&big("-9223372036854775808")), Ok(i64::MIN));
+        assert!(i64::try_from(&big("9223372036854775808")).is_err());
+        assert_eq!(BigInt::from(i128::MIN).to_string(), i128::MIN.to_string());
+    }
+
+    #[test]
+    fn divides_with_multiple_limbs() {
+        let a = big("123456789012345678901234567890123456789");
+        let b = big("-9876543210987654321");
+        let (quot, rem) = a.checked_div_rem(&b).unwrap();
+
+        assert_eq!(quot.clone() * b.clone() + rem.clone(), a);
+        assert!(rem.abs() < b.abs());
+        assert_eq!((a.clone() * b.clone()).exact_div(&b), Some(a.clone()));
+        assert_eq!(a.exact_div(&BigInt::default()), None);
+        assert_eq!(
+            (a.clone() * big("6")).gcd(&(b * big("-4"))),
+            a.gcd(&big("9876543210987654321")) * big("2")
+        );
+    }
+
+    #[test]
+    fn works_as_a_coefficient() {
+        // Coefficients of the reduced basis grow past i64 before being divided out
+        let generators = ["3000000000a^2 - b", "3000000000b^2 - a"];
+        let small = generators.map(|s| s.parse::<Polynomial<i64, 2>>().unwrap());
+        assert!(Basis::new(&small, Lex).is_err());
+
+        let polys = generators.map(|s| s.parse::<Polynomial<BigInt, 2>>().unwrap());
+        let basis = Basis::new(&polys, Lex).unwrap();
+        assert!(basis.contains(&polys[0]).unwrap());
+        assert!(basis.contains(&polys[1]).unwrap());
+
+        let half: Rational<BigInt> = "1/2".parse().unwrap();
+        assert_eq!((half.clone() * half).to_string(), "1/4");
+    }
+
+    #[test]
+    fn parses_coefficients_beyond_i64() {
+        let poly: Polynomial<BigInt> = "10000000000000000000a - 20000000000000000000"
+            .parse()
+            .unwrap();
+        assert_eq!(poly.monomials()[0].coefficient, big("10000000000000000000"));
+        assert_eq!(
+            poly.monomials()[1].coefficient,
+            big("-20000000000000000000")
+        );
+
+        let square = &poly * &poly;
+        assert_eq!(square.to_string().parse::<Polynomial<BigInt>>(), Ok(square));
+
+        let error = "10000000000000000000a".parse::<Polynomial>().unwrap_err();
+        assert_eq!(error.kind, ParseError::Overflow(OverflowError::Coefficient));
+        let error = "a^10000000000000000000"
+            .parse::<Polynomial<BigInt>>()
+            .unwrap_err();
+        assert_eq!(error.kind, ParseError::Overflow(OverflowError::Exponent));
+    }
+
+    #[test]
+    fn computes_polynomial_gcds() {
+        let parse = |s: &str| s.parse::<Polynomial<BigInt, 2>>().unwrap();
+
+        // Both within reach of the modular algorithm and beyond `i128`
+        for s in [
+            "-1000000000000000000000000000000",
+            "10000000000000000000000000000000000000000",
+        ]
+        .iter()
+        {
+            let common = iter::once(Monomial::new(big(s), [1, 1]))
+                .collect::<Polynomial<BigInt, 2>>()
+                + parse("1");
+            let a = &common * &parse("a - 3");
+            let b = &common * &parse("(a + 5)(b - 1)");
+
+            let expected = if s.starts_with('-') {
+                -&common
+            } else {
+                common.clone()
+            };
+            assert_eq!(a.gcd(&b), expected);
+        }
+    }
+
+    #[test]
+    fn factors_and_computes_resultants() {
+        let parse = |s: &str| s.parse::<Polynomial<BigInt, 2>>().unwrap();
+
+        // The constant term is beyond `i64`
+        let poly = parse("-(a - 10000000000)(a + 10000000000)(a^2 + 3)");
+        let factorization = poly.factor().unwrap();
+        assert_eq!(factorization.sign, BigInt::from(-1));
+        assert_eq!(factorization.factors.len(), 3);
+        assert_eq!(factorization.expand(), poly);
+
+        let poly = parse("1000000000000000000000000(a + 1)^2b");
+        let factorization = poly.factor().unwrap();
+        assert_eq!(factorization.content, big("1000000000000000000000000"));
+        assert_eq!(
+            factorization.factors,
+            vec![(parse("b"), 1), (parse("a + 1"), 2)]
+        );
+
+        let a = parse("4000000000a^3 - 1");
+        let expected = parse("-15999999999999999999");
+        assert_eq!(a.resultant(&parse("a^2 - 1"), 0), Ok(expected));
+    }
+}
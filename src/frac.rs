@@ -1,8 +1,19 @@
-use crate::Gcd;
+#[cfg(feature = "alloc")]
+use crate::{order::Lex, Polynomial};
+use crate::{
+    ring::{ExactDiv, Ring},
+    Gcd, Monomial, Zero,
+};
 use core::fmt;
-use std::{fmt::Display, ops::DivAssign};
+#[cfg(feature = "alloc")]
+use core::iter;
+use core::{
+    fmt::Display,
+    ops::{Add, Div, DivAssign, Mul, Neg, Sub},
+};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// A quotient of two values, kept reduced by [`Gcd`] when combined
+#[derive(Clone, Copy, Debug)]
 pub struct Fraction<T, U> {
     pub numerator: T,
     pub denominator: U,
@@ -11,7 +22,7 @@ pub struct Fraction<T, U> {
 impl<T, U> Fraction<T, U>
 where
     T: Gcd<U> + DivAssign<<T as Gcd<U>>::Output>,
-    T::Output: Clone,
+    T::Output: Clone + Zero,
     U: DivAssign<<T as Gcd<U>>::Output>,
 {
     pub fn new(numerator: T, denominator: U) -> Self {
@@ -22,6 +33,7 @@ where
     }
 
     /// ```
+    /// # #[cfg(feature = "alloc")] {
     /// use embedded_algebra::{Fraction, Monomial, Polynomial};
     ///
     /// let poly = Polynomial::from("4a^2 + 2a");
@@ -30,9 +42,21 @@ where
     /// frac.simplify();
     ///
     /// assert_eq!(frac, Fraction::from(Polynomial::from("2a + 1")));
+    /// # }
+    /// ```
+    ///
+    /// A fraction whose numerator and denominator are both zero is left as it is
+    /// ```
+    /// use embedded_algebra::Fraction;
+    ///
+    /// let frac = Fraction::new(0, 0).into_simplified();
+    /// assert_eq!((frac.numerator, frac.denominator), (0, 0));
     /// ```
     pub fn simplify(&mut self) {
         let gcd = self.numerator.gcd(&self.denominator);
+        if gcd.is_zero() {
+            return;
+        }
         self.numerator /= gcd.clone();
         self.denominator /= gcd;
     }
@@ -46,7 +70,7 @@ where
 impl<T, U> From<T> for Fraction<T, U>
 where
     T: Gcd<U> + DivAssign<<T as Gcd<U>>::Output>,
-    T::Output: Clone,
+    T::Output: Clone + Zero,
     U: DivAssign<<T as Gcd<U>>::Output> + Default,
 {
     fn from(numerator: T) -> Self {
@@ -54,6 +78,339 @@ where
     }
 }
 
+/// The arithmetic needed to compare fractions without overflowing
+trait Domain: Sized + PartialEq {
+    fn is_zero(&self) -> bool;
+
+    fn gcd(&self, rhs: &Self) -> Self;
+
+    /// Divide by a nonzero divisor of this
+    fn exact_div(&self, rhs: &Self) -> Self;
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+}
+
+impl<C> Domain for C
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    fn is_zero(&self) -> bool {
+        Ring::is_zero(self)
+    }
+
+    fn gcd(&self, rhs: &Self) -> Self {
+        Gcd::gcd(self, rhs)
+    }
+
+    fn exact_div(&self, rhs: &Self) -> Self {
+        ExactDiv::exact_div(self, rhs).expect("divisor does not divide")
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Ring::checked_mul(self, rhs)
+    }
+}
+
+impl<C, const N: usize> Domain for Monomial<C, N>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    fn is_zero(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    fn gcd(&self, rhs: &Self) -> Self {
+        self.clone().gcd(rhs.clone())
+    }
+
+    fn exact_div(&self, rhs: &Self) -> Self {
+        self.clone()
+            .checked_div(rhs.clone())
+            .expect("divisor does not divide")
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        self.clone().checked_mul(rhs.clone()).ok()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> Domain for Polynomial<C, N>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    fn is_zero(&self) -> bool {
+        Polynomial::is_zero(self)
+    }
+
+    fn gcd(&self, rhs: &Self) -> Self {
+        Gcd::gcd(self, rhs)
+    }
+
+    fn exact_div(&self, rhs: &Self) -> Self {
+        self.div_rem(rhs, Lex).0.into_canonical()
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Polynomial::checked_mul(self, rhs)
+            .ok()
+            .map(Polynomial::into_canonical)
+    }
+}
+
+/// Divide out the greatest common divisor of `a` and a nonzero `b`
+fn reduce<X: Domain>(a: &X, b: &X) -> (X, X) {
+    let gcd = a.gcd(b);
+    (a.exact_div(&gcd), b.exact_div(&gcd))
+}
+
+/// Whether `a / b == c / d`, where a zero denominator is unequal to everything
+fn equal<X: Domain>(a: &X, b: &X, c: &X, d: &X) -> bool {
+    if b.is_zero() || d.is_zero() {
+        return false;
+    }
+
+    // Equal reduced fractions differ only by a unit, so once the common factors of
+    // the numerators and of the denominators are divided out, both cross products
+    // are units and an overflow means the fractions differ
+    let (a, b) = reduce(a, b);
+    let (c, d) = reduce(c, d);
+    let numerators = a.gcd(&c);
+    if numerators.is_zero() {
+        return true;
+    }
+    let denominators = b.gcd(&d);
+    let (a, c) = (a.exact_div(&numerators), c.exact_div(&numerators));
+    let (b, d) = (b.exact_div(&denominators), d.exact_div(&denominators));
+    match (a.checked_mul(&d), c.checked_mul(&b)) {
+        (Some(lhs), Some(rhs)) => lhs == rhs,
+        _ => false,
+    }
+}
+
+impl<C> PartialEq for Fraction<C, C>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    /// Compare without requiring the fractions to be reduced or overflowing.
+    /// A fraction with a zero denominator is not equal to anything, including itself.
+    /// ```
+    /// use embedded_algebra::Fraction;
+    ///
+    /// assert_eq!(Fraction::new(2, 4), Fraction::new(-1, -2));
+    /// assert_eq!(Fraction::new(i64::MAX, i64::MAX), Fraction::new(1, 1));
+    /// assert_ne!(Fraction::new(0, 0), Fraction::new(1, 2));
+    /// assert_eq!(Fraction::new(i64::MIN, 1), Fraction::new(i64::MIN, 1));
+    /// assert_eq!(Fraction::new(i64::MIN, i64::MIN), Fraction::new(-1, -1));
+    /// assert_ne!(Fraction::new(i64::MIN, 1), Fraction::new(i64::MAX, 1));
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        equal(
+            &self.numerator,
+            &self.denominator,
+            &other.numerator,
+            &other.denominator,
+        )
+    }
+}
+
+impl<C, const N: usize> PartialEq for Fraction<Monomial<C, N>, Monomial<C, N>>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    /// Compare like fractions of integers, by the greatest common divisors of the monomials
+    /// ```
+    /// use embedded_algebra::{Fraction, Monomial};
+    ///
+    /// assert_eq!(
+    ///     Fraction::new(Monomial::from("4a^2b"), Monomial::from("6ab^3")),
+    ///     Fraction::new(Monomial::from("-2a"), Monomial::from("-3b^2"))
+    /// );
+    /// assert_ne!(
+    ///     Fraction::new(Monomial::from("a"), Monomial::from("b")),
+    ///     Fraction::new(Monomial::from("b"), Monomial::from("a"))
+    /// );
+    /// assert_ne!(
+    ///     Fraction::new(Monomial::from("0a"), Monomial::from("0b")),
+    ///     Fraction::new(Monomial::from("0a"), Monomial::from("0b"))
+    /// );
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        equal(
+            &self.numerator,
+            &self.denominator,
+            &other.numerator,
+            &other.denominator,
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize, T, U> PartialEq for Fraction<Polynomial<C, N, T>, Polynomial<C, N, U>>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    T: AsRef<[Monomial<C, N>]>,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    /// Compare like fractions of integers, by the greatest common divisors of the polynomials
+    /// ```
+    /// use embedded_algebra::{Fraction, Polynomial};
+    ///
+    /// assert_eq!(
+    ///     Fraction::new(Polynomial::from("a^2 - 1"), Polynomial::from("a - 1")),
+    ///     Fraction::new(Polynomial::from("2a + 2"), Polynomial::from("2"))
+    /// );
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        equal(
+            &canonical(&self.numerator),
+            &canonical(&self.denominator),
+            &canonical(&other.numerator),
+            &canonical(&other.denominator),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize, T> PartialEq for Fraction<Polynomial<C, N, T>, Monomial<C, N>>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let monomial = |monomial: &Monomial<C, N>| iter::once(monomial.clone()).collect();
+        equal(
+            &canonical(&self.numerator),
+            &monomial(&self.denominator),
+            &canonical(&other.numerator),
+            &monomial(&other.denominator),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn canonical<C, const N: usize, T>(poly: &Polynomial<C, N, T>) -> Polynomial<C, N>
+where
+    C: ExactDiv,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    poly.monomials()
+        .iter()
+        .cloned()
+        .collect::<Polynomial<C, N>>()
+        .into_canonical()
+}
+
+impl<T, U> Add for Fraction<T, U>
+where
+    T: Gcd<U> + DivAssign<<T as Gcd<U>>::Output> + Mul<U, Output = T> + Add<Output = T>,
+    <T as Gcd<U>>::Output: Clone + Zero,
+    U: DivAssign<<T as Gcd<U>>::Output> + Mul<Output = U> + Clone,
+{
+    type Output = Self;
+
+    /// Add over a common denominator and reduce the result
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{Fraction, Polynomial};
+    ///
+    /// let a = Fraction::new(Polynomial::from("1"), Polynomial::from("a + 1"));
+    /// let b = Fraction::new(Polynomial::from("1"), Polynomial::from("a - 1"));
+    /// let sum = a + b;
+    ///
+    /// assert_eq!(sum.numerator, Polynomial::from("2a"));
+    /// assert_eq!(sum.denominator, Polynomial::from("a^2 - 1"));
+    /// # }
+    /// ```
+    fn add(self, rhs: Self) -> Self::Output {
+        let numerator =
+            self.numerator * rhs.denominator.clone() + rhs.numerator * self.denominator.clone();
+        Self::new(numerator, self.denominator * rhs.denominator).into_simplified()
+    }
+}
+
+impl<T, U> Sub for Fraction<T, U>
+where
+    T: Gcd<U> + DivAssign<<T as Gcd<U>>::Output> + Mul<U, Output = T> + Sub<Output = T>,
+    <T as Gcd<U>>::Output: Clone + Zero,
+    U: DivAssign<<T as Gcd<U>>::Output> + Mul<Output = U> + Clone,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let numerator =
+            self.numerator * rhs.denominator.clone() - rhs.numerator * self.denominator.clone();
+        Self::new(numerator, self.denominator * rhs.denominator).into_simplified()
+    }
+}
+
+impl<T, U> Mul for Fraction<T, U>
+where
+    T: Gcd<U> + DivAssign<<T as Gcd<U>>::Output> + Mul<Output = T>,
+    <T as Gcd<U>>::Output: Clone + Zero,
+    U: DivAssign<<T as Gcd<U>>::Output> + Mul<Output = U>,
+{
+    type Output = Self;
+
+    /// Multiply numerators and denominators and reduce the result
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{Fraction, Monomial, Polynomial};
+    ///
+    /// let a = Fraction::new(Polynomial::from("a^2 - 1"), Monomial::from("2b"));
+    /// let b = Fraction::new(Polynomial::from("b"), Monomial::from("a"));
+    ///
+    /// assert_eq!(
+    ///     a * b,
+    ///     Fraction::new(Polynomial::from("a^2 - 1"), Monomial::from("2a"))
+    /// );
+    /// # }
+    /// ```
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+        .into_simplified()
+    }
+}
+
+impl<T> Div for Fraction<T, T>
+where
+    T: Gcd<T> + DivAssign<<T as Gcd<T>>::Output> + Mul<Output = T>,
+    <T as Gcd<T>>::Output: Clone + Zero,
+{
+    type Output = Self;
+
+    /// Multiply by the reciprocal of `rhs` and reduce the result
+    /// ```
+    /// use embedded_algebra::Fraction;
+    ///
+    /// assert_eq!(Fraction::new(3, 4) / Fraction::new(9, 2), Fraction::new(1, 6));
+    /// ```
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+        .into_simplified()
+    }
+}
+
+impl<T, U> Neg for Fraction<T, U>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
 impl<T, U> fmt::Display for Fraction<T, U>
 where
     T: Display,
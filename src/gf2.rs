@@ -0,0 +1,619 @@
+//! Bit-packed univariate polynomials over GF(2), for CRCs and LFSRs
+//!
+//! Bit `i` of word `i / W::BITS` holds the coefficient of `x^i`, so the CRC-8 generator
+//! `x^8 + x^2 + x + 1` is `0x107`. The functions here work in place on word slices of any length
+//! without allocating, and [`Gf2`] wraps a single word with the usual operators.
+
+use crate::{CapacityError, Gcd, ModInt, Monomial, Polynomial};
+use core::{
+    mem,
+    ops::{Add, BitAnd, BitXor, BitXorAssign, Div, Mul, Rem, Shl, Shr, Sub},
+};
+
+/// An unsigned integer holding `BITS` coefficients
+pub trait Word:
+    Copy
+    + Eq
+    + BitAnd<Output = Self>
+    + BitXor<Output = Self>
+    + BitXorAssign
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+{
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn leading_zeros(self) -> u32;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn leading_zeros(self) -> u32 {
+                    <$t>::leading_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64, u128);
+
+fn bit<W: Word>(a: &[W], i: usize) -> bool {
+    a.get(i / W::BITS)
+        .is_some_and(|&word| (word >> (i % W::BITS)) & W::ONE != W::ZERO)
+}
+
+fn set_bit<W: Word>(a: &mut [W], i: usize) {
+    a[i / W::BITS] ^= W::ONE << (i % W::BITS);
+}
+
+fn clear<W: Word>(a: &mut [W]) {
+    for word in a.iter_mut() {
+        *word = W::ZERO;
+    }
+}
+
+/// Add `src * x^shift` to `dst`, dropping any bits past the end of `dst`
+fn xor_shifted<W: Word>(dst: &mut [W], src: &[W], shift: usize) {
+    let (words, bits) = (shift / W::BITS, shift % W::BITS);
+    for (i, &word) in src.iter().enumerate() {
+        if let Some(dst) = dst.get_mut(i + words) {
+            *dst ^= word << bits;
+        }
+        if bits > 0 {
+            if let Some(dst) = dst.get_mut(i + words + 1) {
+                *dst ^= word >> (W::BITS - bits);
+            }
+        }
+    }
+}
+
+/// The highest power of `x`, or `None` for the zero polynomial
+/// ```
+/// use embedded_algebra::gf2;
+///
+/// assert_eq!(gf2::degree(&[0x07u8, 0x01]), Some(8));
+/// assert_eq!(gf2::degree::<u32>(&[0, 0]), None);
+/// ```
+pub fn degree<W: Word>(a: &[W]) -> Option<usize> {
+    let (i, word) = a
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, &word)| word != W::ZERO)?;
+    Some(i * W::BITS + W::BITS - 1 - word.leading_zeros() as usize)
+}
+
+/// Multiply `lhs` by `rhs` into `out`, or return an error if the product does not fit
+/// ```
+/// use embedded_algebra::gf2;
+///
+/// let mut out = [0u8; 2];
+/// gf2::mul(&[0b11], &[0x81], &mut out)?;
+/// assert_eq!(out, [0x83, 0x01]);
+/// # Ok::<(), embedded_algebra::CapacityError>(())
+/// ```
+pub fn mul<W: Word>(lhs: &[W], rhs: &[W], out: &mut [W]) -> Result<(), CapacityError> {
+    clear(out);
+    let (lhs_degree, rhs_degree) = match (degree(lhs), degree(rhs)) {
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+        _ => return Ok(()),
+    };
+    if lhs_degree + rhs_degree >= out.len() * W::BITS {
+        return Err(CapacityError);
+    }
+
+    for i in (0..=lhs_degree).filter(|&i| bit(lhs, i)) {
+        xor_shifted(out, rhs, i);
+    }
+    Ok(())
+}
+
+/// Reduce `lhs` in place modulo `divisor`
+/// # Panics
+/// If `divisor` is zero
+pub fn rem<W: Word>(lhs: &mut [W], divisor: &[W]) {
+    let divisor_degree = degree(divisor).expect("attempt to divide by a zero polynomial");
+    while let Some(lhs_degree) = degree(lhs).filter(|&d| d >= divisor_degree) {
+        xor_shifted(lhs, divisor, lhs_degree - divisor_degree);
+    }
+}
+
+/// Divide `lhs` by `divisor`, leaving the remainder in `lhs` and writing the quotient,
+/// or return an error without changing `lhs` if the quotient does not fit
+/// ```
+/// use embedded_algebra::gf2;
+///
+/// // Append the CRC-8 of the message byte 0x82
+/// let mut codeword = [0x00u8, 0x82];
+/// let mut quotient = [0u8; 2];
+/// gf2::div_rem(&mut codeword, &[0x07, 0x01], &mut quotient)?;
+/// assert_eq!(codeword, [0x87, 0x00]);
+/// # Ok::<(), embedded_algebra::CapacityError>(())
+/// ```
+/// # Panics
+/// If `divisor` is zero
+pub fn div_rem<W: Word>(
+    lhs: &mut [W],
+    divisor: &[W],
+    quotient: &mut [W],
+) -> Result<(), CapacityError> {
+    let divisor_degree = degree(divisor).expect("attempt to divide by a zero polynomial");
+    if degree(lhs).is_some_and(|d| d >= divisor_degree + quotient.len() * W::BITS) {
+        return Err(CapacityError);
+    }
+
+    clear(quotient);
+    while let Some(lhs_degree) = degree(lhs).filter(|&d| d >= divisor_degree) {
+        xor_shifted(lhs, divisor, lhs_degree - divisor_degree);
+        set_bit(quotient, lhs_degree - divisor_degree);
+    }
+    Ok(())
+}
+
+/// The greatest common divisor by Euclid's algorithm, overwriting both `a` and `b`
+/// and returning whichever holds the result
+/// ```
+/// use embedded_algebra::gf2;
+///
+/// // (x + 1)(x^2 + x + 1) and (x + 1)^2
+/// let (mut a, mut b) = ([0b1001u32], [0b101u32]);
+/// assert_eq!(gf2::gcd(&mut a, &mut b), &[0b11]);
+/// ```
+pub fn gcd<'a, W: Word>(mut a: &'a mut [W], mut b: &'a mut [W]) -> &'a [W] {
+    while degree(b).is_some() {
+        rem(a, b);
+        mem::swap(&mut a, &mut b);
+    }
+    a
+}
+
+/// Test whether `f` has no factors besides itself and `1` by Ben-Or's algorithm,
+/// using `scratch` of at least five times the length of `f`
+/// ```
+/// use embedded_algebra::gf2;
+///
+/// let mut scratch = [0u64; 10];
+/// assert_eq!(gf2::is_irreducible(&[0x1B, 0x1], &mut scratch), Ok(true));
+/// assert_eq!(gf2::is_irreducible(&[0x1F, 0x1], &mut scratch), Ok(false));
+/// ```
+pub fn is_irreducible<W: Word>(f: &[W], scratch: &mut [W]) -> Result<bool, CapacityError> {
+    let len = f.len();
+    if scratch.len() < 5 * len {
+        return Err(CapacityError);
+    }
+    let n = match degree(f) {
+        Some(0) | None => return Ok(false),
+        Some(1) => return Ok(true),
+        Some(n) => n,
+    };
+
+    let (power, scratch) = scratch.split_at_mut(len);
+    let (square, scratch) = scratch.split_at_mut(2 * len);
+    let (a, scratch) = scratch.split_at_mut(len);
+    let b = &mut scratch[..len];
+
+    // Any factor of degree d divides x^(2^d) - x, so check each degree up to half of n
+    clear(power);
+    set_bit(power, 1);
+    for _ in 0..n / 2 {
+        mul(power, power, square)?;
+        rem(square, f);
+        power.copy_from_slice(&square[..len]);
+
+        a.copy_from_slice(power);
+        set_bit(a, 1);
+        b.copy_from_slice(f);
+        if degree(gcd(a, b)) != Some(0) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Write the terms of `poly` in the variable `var` into `out`, or return `None`
+/// if another variable appears or the degree does not fit, leaving `out` cleared
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use embedded_algebra::{gf2, ModInt, Polynomial};
+///
+/// let poly: Polynomial<ModInt<2>> = "a^8 + a^2 + a + 1".parse().unwrap();
+/// let mut crc8 = [0u8; 2];
+///
+/// assert_eq!(gf2::from_polynomial(&poly, 0, &mut crc8), Some(()));
+/// assert_eq!(crc8, [0x07, 0x01]);
+/// assert_eq!(gf2::from_polynomial(&poly, 0, &mut crc8[..1]), None);
+/// assert_eq!(gf2::from_polynomial(&poly, 1, &mut crc8), None);
+/// assert_eq!(gf2::to_polynomial::<_, 4>(&crc8, 0), Polynomial::zero());
+/// # }
+/// ```
+pub fn from_polynomial<W: Word, const N: usize, T>(
+    poly: &Polynomial<ModInt<2>, N, T>,
+    var: usize,
+    out: &mut [W],
+) -> Option<()>
+where
+    T: AsRef<[Monomial<ModInt<2>, N>]>,
+{
+    clear(out);
+    for monomial in poly.monomials() {
+        let exp = usize::from(monomial.exponents[var]);
+        let other = (0..N).any(|v| v != var && monomial.exponents[v] > 0);
+        let one = monomial.coefficient.value() == 1;
+        if other || (one && exp >= out.len() * W::BITS) {
+            clear(out);
+            return None;
+        }
+
+        if one {
+            set_bit(out, exp);
+        }
+    }
+    Some(())
+}
+
+/// Write `a` as a polynomial in the variable `var`
+/// ```
+/// use embedded_algebra::{gf2, ModInt, Polynomial};
+///
+/// let poly: Polynomial<ModInt<2>> = gf2::to_polynomial(&[0x07u8, 0x01], 0);
+/// assert_eq!(poly, "a^8 + a^2 + a + 1".parse().unwrap());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_polynomial<W: Word, const N: usize>(a: &[W], var: usize) -> Polynomial<ModInt<2>, N> {
+    (0..a.len() * W::BITS)
+        .rev()
+        .filter(|&exp| bit(a, exp))
+        .map(|exp| Monomial::coefficient(ModInt::new(1)).exponent(var, exp as u16))
+        .collect()
+}
+
+/// A polynomial over GF(2) of degree less than `W::BITS`, packed into a single word
+/// ```
+/// use embedded_algebra::gf2::Gf2;
+///
+/// let crc8 = Gf2(0x107u16);
+///
+/// assert_eq!(crc8.degree(), Some(8));
+/// assert_eq!(Gf2(0x8200u16) % crc8, Gf2(0x87));
+/// assert_eq!(Gf2(0b11u16) * Gf2(0b11), Gf2(0b101));
+/// assert!(Gf2(0x11Du16).is_irreducible());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gf2<W = u64>(pub W);
+
+impl<W: Word> Gf2<W> {
+    /// The highest power of `x`, or `None` for the zero polynomial
+    pub fn degree(self) -> Option<u32> {
+        degree(&[self.0]).map(|d| d as u32)
+    }
+
+    /// The full product as its low and high words
+    pub fn widening_mul(self, rhs: Self) -> (Self, Self) {
+        let mut out = [W::ZERO; 2];
+        mul(&[self.0], &[rhs.0], &mut out).unwrap();
+        (Self(out[0]), Self(out[1]))
+    }
+
+    /// Multiply by `rhs`, or return `None` if the product does not fit in a word
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.widening_mul(rhs) {
+            (low, Gf2(high)) if high == W::ZERO => Some(low),
+            _ => None,
+        }
+    }
+
+    /// The quotient and remainder of dividing by `divisor`
+    /// # Panics
+    /// If `divisor` is zero
+    pub fn div_rem(self, divisor: Self) -> (Self, Self) {
+        let (mut lhs, mut quotient) = ([self.0], [W::ZERO]);
+        div_rem(&mut lhs, &[divisor.0], &mut quotient).unwrap();
+        (Self(quotient[0]), Self(lhs[0]))
+    }
+
+    /// Multiply by `rhs` modulo `modulus`
+    /// # Panics
+    /// If `modulus` is zero
+    pub fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+        let mut product = [W::ZERO; 2];
+        mul(&[self.0], &[rhs.0], &mut product).unwrap();
+        rem(&mut product, &[modulus.0]);
+        Self(product[0])
+    }
+
+    /// Raise this to the power `exp` modulo `modulus` by repeated squaring
+    /// ```
+    /// use embedded_algebra::gf2::Gf2;
+    ///
+    /// // x has order 15 modulo the primitive x^4 + x + 1
+    /// let (x, f) = (Gf2(0b10u8), Gf2(0b10011));
+    /// assert_eq!(x.pow_mod(15, f), Gf2(1));
+    /// assert_eq!(x.pow_mod(5, f), Gf2(0b110));
+    /// ```
+    /// # Panics
+    /// If `modulus` is zero
+    pub fn pow_mod(self, mut exp: u64, modulus: Self) -> Self {
+        let mut base = self % modulus;
+        let mut acc = Self(W::ONE) % modulus;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc.mul_mod(base, modulus);
+            }
+            base = base.mul_mod(base, modulus);
+            exp >>= 1;
+        }
+        acc
+    }
+
+    /// Returns `true` if this has no factors besides itself and `1`
+    pub fn is_irreducible(self) -> bool {
+        is_irreducible(&[self.0], &mut [W::ZERO; 5]).unwrap()
+    }
+
+    /// Returns `true` if this is irreducible and `x` generates every nonzero residue,
+    /// so an LFSR with these taps runs through all `2^n - 1` nonzero states,
+    /// or `None` if the degree is above 64
+    /// ```
+    /// use embedded_algebra::gf2::Gf2;
+    ///
+    /// assert_eq!(Gf2(0x11Du32).is_primitive(), Some(true));
+    /// // The AES polynomial is irreducible, but x only has order 51
+    /// assert_eq!(Gf2(0x11Bu32).is_primitive(), Some(false));
+    /// ```
+    pub fn is_primitive(self) -> Option<bool> {
+        let n = match self.degree() {
+            Some(n) if n > 64 => return None,
+            Some(n) if n > 0 => n,
+            _ => return Some(false),
+        };
+        if !self.is_irreducible() {
+            return Some(false);
+        }
+
+        let order = u64::MAX >> (64 - n);
+        let x = Self(W::ONE << 1);
+        let is_one = |exp| x.pow_mod(exp, self) == Self(W::ONE);
+
+        let mut primes = [0; 64];
+        let len = factor::prime_factors(order, &mut primes);
+        Some(is_one(order) && primes[..len].iter().all(|&q| !is_one(order / q)))
+    }
+
+    /// Collect the terms of `poly` in the variable `var`, or return `None`
+    /// if another variable appears or the degree does not fit
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{gf2::Gf2, ModInt, Polynomial};
+    ///
+    /// let poly: Polynomial<ModInt<2>> = "b^3 + b + 1".parse().unwrap();
+    ///
+    /// assert_eq!(Gf2::from_polynomial(&poly, 1), Some(Gf2(0b1011u8)));
+    /// assert_eq!(Gf2::<u8>::from_polynomial(&poly, 0), None);
+    /// assert_eq!(Gf2(0b1011u8).to_polynomial(1), poly);
+    /// # }
+    /// ```
+    pub fn from_polynomial<const N: usize, T>(
+        poly: &Polynomial<ModInt<2>, N, T>,
+        var: usize,
+    ) -> Option<Self>
+    where
+        T: AsRef<[Monomial<ModInt<2>, N>]>,
+    {
+        let mut word = [W::ZERO];
+        from_polynomial(poly, var, &mut word).map(|()| Self(word[0]))
+    }
+
+    /// Write this as a polynomial in the variable `var`
+    #[cfg(feature = "alloc")]
+    pub fn to_polynomial<const N: usize>(self, var: usize) -> Polynomial<ModInt<2>, N> {
+        to_polynomial(&[self.0], var)
+    }
+}
+
+impl<W: Word> Add for Gf2<W> {
+    type Output = Self;
+
+    /// Coefficients are added modulo two, which is exclusive or
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl<W: Word> Sub for Gf2<W> {
+    type Output = Self;
+
+    /// Subtraction is the same as addition in characteristic two
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + rhs
+    }
+}
+
+impl<W: Word> Mul for Gf2<W> {
+    type Output = Self;
+
+    /// # Panics
+    /// If the product does not fit in a word
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl<W: Word> Div for Gf2<W> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<W: Word> Rem for Gf2<W> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        let mut lhs = [self.0];
+        rem(&mut lhs, &[rhs.0]);
+        Self(lhs[0])
+    }
+}
+
+impl<W: Word> Gcd for Gf2<W> {
+    type Output = Self;
+
+    fn gcd(&self, rhs: &Self) -> Self::Output {
+        let (mut a, mut b) = ([self.0], [rhs.0]);
+        Self(gcd(&mut a, &mut b)[0])
+    }
+}
+
+/// Factoring `2^n - 1` to find the possible orders of `x`
+mod factor {
+    fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+        (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+    }
+
+    fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+        let mut acc = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = mul_mod(acc, base, m);
+            }
+            base = mul_mod(base, base, m);
+            exp >>= 1;
+        }
+        acc
+    }
+
+    /// Deterministic Miller-Rabin, using bases that suffice below 2^64
+    fn is_prime(n: u64) -> bool {
+        const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+        if n < 2 {
+            return false;
+        }
+        if let Some(&p) = BASES.iter().find(|&&p| n % p == 0) {
+            return n == p;
+        }
+
+        let s = (n - 1).trailing_zeros();
+        let d = (n - 1) >> s;
+        BASES.iter().all(|&a| {
+            let mut x = pow_mod(a, d, n);
+            if x == 1 || x == n - 1 {
+                return true;
+            }
+            (1..s).any(|_| {
+                x = mul_mod(x, x, n);
+                x == n - 1
+            })
+        })
+    }
+
+    fn gcd(mut a: u64, mut b: u64) -> u64 {
+        while b != 0 {
+            a %= b;
+            core::mem::swap(&mut a, &mut b);
+        }
+        a
+    }
+
+    /// A nontrivial divisor of an odd composite `n` by Pollard's rho method
+    fn divisor(n: u64) -> u64 {
+        for c in 1.. {
+            let f = |x| (mul_mod(x, x, n) + c) % n;
+            let (mut x, mut y, mut d) = (2, 2, 1);
+            while d == 1 {
+                x = f(x);
+                y = f(f(y));
+                d = gcd(x.abs_diff(y), n);
+            }
+            if d != n {
+                return d;
+            }
+        }
+        unreachable!()
+    }
+
+    fn push(primes: &mut [u64], len: &mut usize, n: u64) {
+        if is_prime(n) {
+            if !primes[..*len].contains(&n) {
+                primes[*len] = n;
+                *len += 1;
+            }
+        } else {
+            let d = divisor(n);
+            push(primes, len, d);
+            push(primes, len, n / d);
+        }
+    }
+
+    /// Write the distinct prime factors of `n` into `primes`, returning how many there are
+    pub(super) fn prime_factors(mut n: u64, primes: &mut [u64; 64]) -> usize {
+        let mut len = 0;
+        for p in 2..1000 {
+            if n % p == 0 {
+                primes[len] = p;
+                len += 1;
+                while n % p == 0 {
+                    n /= p;
+                }
+            }
+        }
+        if n > 1 {
+            push(primes, &mut len, n);
+        }
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{factor, Gf2};
+
+    #[test]
+    fn finds_primitive_polynomials() {
+        // x^4 + x^3 + x^2 + x + 1 is irreducible, but x has order 5
+        assert!(Gf2(0b11111u8).is_irreducible());
+        assert_eq!(Gf2(0b11111u8).is_primitive(), Some(false));
+        assert_eq!(Gf2(0b10011u8).is_primitive(), Some(true));
+        assert_eq!(Gf2(0b11u8).is_primitive(), Some(true));
+        assert_eq!(Gf2(0b10u8).is_primitive(), Some(false));
+
+        // x^64 + x^4 + x^3 + x + 1
+        let f = Gf2((1u128 << 64) | 0x1B);
+        assert_eq!(f.is_primitive(), Some(true));
+        assert_eq!(Gf2(1u128 << 65).is_primitive(), None);
+    }
+
+    #[test]
+    fn factors_mersenne_numbers() {
+        let mut primes = [0; 64];
+        let len = factor::prime_factors(u64::MAX, &mut primes);
+        assert_eq!(&primes[..len], &[3, 5, 17, 257, 641, 65537, 6_700_417]);
+
+        let len = factor::prime_factors((1 << 59) - 1, &mut primes);
+        assert_eq!(&primes[..len], &[179_951, 3_203_431_780_337]);
+    }
+
+    #[test]
+    fn multiplies_across_words() {
+        // (x^31 + 1)(x^31 + x + 1) = x^62 + x^32 + x + 1
+        let (lhs, rhs) = (Gf2(0x8000_0001u32), Gf2(0x8000_0003));
+
+        assert_eq!(lhs.widening_mul(rhs), (Gf2(0b11), Gf2(0x4000_0001)));
+        assert_eq!(lhs.checked_mul(rhs), None);
+        assert_eq!(
+            Gf2(0x8000_0001u64) * Gf2(0x8000_0003),
+            Gf2(0x4000_0001_0000_0003)
+        );
+    }
+}
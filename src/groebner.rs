@@ -0,0 +1,431 @@
+//! Groebner bases of polynomial ideals by Buchberger's algorithm
+//!
+//! Coefficients are kept fraction-free: every polynomial is scaled to be primitive
+//! with a positive leading coefficient, so integer coefficients describe the same
+//! basis as over the rationals.
+
+use crate::{
+    order::{Lex, MonomialOrder},
+    ring::ExactDiv,
+    Gcd, Monomial, OverflowError, Polynomial,
+};
+use alloc::vec::Vec;
+use core::mem;
+
+/// Nonzero terms with distinct exponents, sorted by descending order
+type Terms<C, const N: usize> = Vec<Monomial<C, N>>;
+
+fn overflow<T>(value: Option<T>) -> Result<T, OverflowError> {
+    value.ok_or(OverflowError::Coefficient)
+}
+
+fn divides<const N: usize>(lhs: &[u16; N], rhs: &[u16; N]) -> bool {
+    lhs.iter().zip(rhs.iter()).all(|(a, b)| a <= b)
+}
+
+fn lcm<const N: usize>(lhs: &[u16; N], rhs: &[u16; N]) -> [u16; N] {
+    let mut out = *lhs;
+    for (a, &b) in out.iter_mut().zip(rhs.iter()) {
+        *a = (*a).max(b);
+    }
+    out
+}
+
+/// Exponents of `lhs / rhs`, where `rhs` divides `lhs`
+fn quotient<const N: usize>(lhs: &[u16; N], rhs: &[u16; N]) -> [u16; N] {
+    let mut out = *lhs;
+    for (a, &b) in out.iter_mut().zip(rhs.iter()) {
+        *a -= b;
+    }
+    out
+}
+
+/// Combine like terms and sort by descending `order`
+fn normalize<C, O, const N: usize>(
+    mut terms: Terms<C, N>,
+    order: &O,
+) -> Result<Terms<C, N>, OverflowError>
+where
+    C: ExactDiv,
+    O: MonomialOrder,
+{
+    terms.sort_by(|lhs, rhs| order.compare(&rhs.exponents, &lhs.exponents));
+
+    let mut out: Terms<C, N> = Vec::with_capacity(terms.len());
+    for term in terms {
+        match out.last_mut() {
+            Some(last) if last.exponents == term.exponents => {
+                last.coefficient = overflow(last.coefficient.checked_add(&term.coefficient))?;
+            }
+            _ => out.push(term),
+        }
+    }
+    out.retain(|term| !term.coefficient.is_zero());
+    Ok(out)
+}
+
+/// Divide out the content and make the leading coefficient positive
+fn primitive<C, const N: usize>(mut terms: Terms<C, N>) -> Result<Terms<C, N>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    let mut content = terms
+        .iter()
+        .fold(C::zero(), |acc, term| acc.gcd(&term.coefficient));
+    if content.is_zero() {
+        return Ok(terms);
+    }
+    if terms[0].coefficient.is_negative() {
+        content = overflow(content.checked_neg())?;
+    }
+    for term in terms.iter_mut() {
+        term.coefficient = overflow(term.coefficient.exact_div(&content))?;
+    }
+    Ok(terms)
+}
+
+/// Append the terms of `x^shift * scale * terms`
+fn push_scaled<C, const N: usize>(
+    out: &mut Terms<C, N>,
+    terms: &[Monomial<C, N>],
+    scale: &C,
+    shift: &[u16; N],
+) -> Result<(), OverflowError>
+where
+    C: ExactDiv,
+{
+    for term in terms {
+        let coefficient = overflow(term.coefficient.checked_mul(scale))?;
+        let mut exponents = term.exponents;
+        for (exp, &by) in exponents.iter_mut().zip(shift.iter()) {
+            *exp = exp.checked_add(by).ok_or(OverflowError::Exponent)?;
+        }
+        out.push(Monomial::new(coefficient, exponents));
+    }
+    Ok(())
+}
+
+/// Compute `a * x^lhs_shift * lhs - b * x^rhs_shift * rhs`
+fn sub_scaled<C, O, const N: usize>(
+    (lhs, a, lhs_shift): (&[Monomial<C, N>], &C, &[u16; N]),
+    (rhs, b, rhs_shift): (&[Monomial<C, N>], &C, &[u16; N]),
+    order: &O,
+) -> Result<Terms<C, N>, OverflowError>
+where
+    C: ExactDiv,
+    O: MonomialOrder,
+{
+    let mut terms = Vec::with_capacity(lhs.len() + rhs.len());
+    push_scaled(&mut terms, lhs, a, lhs_shift)?;
+    push_scaled(&mut terms, rhs, &overflow(b.checked_neg())?, rhs_shift)?;
+    normalize(terms, order)
+}
+
+/// The multipliers `(a, b)` for which `a * lhs - b * rhs` is zero
+fn cofactors<C>(lhs: &C, rhs: &C) -> Result<(C, C), OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    let gcd = lhs.gcd(rhs);
+    Ok((
+        overflow(rhs.exact_div(&gcd))?,
+        overflow(lhs.exact_div(&gcd))?,
+    ))
+}
+
+fn s_polynomial<C, O, const N: usize>(
+    f: &[Monomial<C, N>],
+    g: &[Monomial<C, N>],
+    order: &O,
+) -> Result<Terms<C, N>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    O: MonomialOrder,
+{
+    let lcm = lcm(&f[0].exponents, &g[0].exponents);
+    let (a, b) = cofactors(&f[0].coefficient, &g[0].coefficient)?;
+    sub_scaled(
+        (f, &a, &quotient(&lcm, &f[0].exponents)),
+        (g, &b, &quotient(&lcm, &g[0].exponents)),
+        order,
+    )
+}
+
+/// Reduce `f` by `basis` until no term is divisible by a leading term,
+/// ignoring empty polynomials in `basis`
+fn reduce<C, O, const N: usize>(
+    mut f: Terms<C, N>,
+    basis: &[Terms<C, N>],
+    order: &O,
+) -> Result<Terms<C, N>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    O: MonomialOrder,
+{
+    let mut remainder: Terms<C, N> = Vec::new();
+    while let Some(lead) = f.first() {
+        let divisor = basis
+            .iter()
+            .filter_map(|g| g.first().map(|lead| (g, lead)))
+            .find(|(_, g)| divides(&g.exponents, &lead.exponents));
+
+        match divisor {
+            Some((g, g_lead)) => {
+                let (a, b) = cofactors(&lead.coefficient, &g_lead.coefficient)?;
+                let shift = quotient(&lead.exponents, &g_lead.exponents);
+                f = sub_scaled((&f, &a, &[0; N]), (g, &b, &shift), order)?;
+                for term in remainder.iter_mut() {
+                    term.coefficient = overflow(term.coefficient.checked_mul(&a))?;
+                }
+
+                // Keep coefficients from growing with every step
+                let content = f
+                    .iter()
+                    .chain(remainder.iter())
+                    .fold(C::zero(), |acc, term| acc.gcd(&term.coefficient));
+                if !content.is_zero() {
+                    for term in f.iter_mut().chain(remainder.iter_mut()) {
+                        term.coefficient = overflow(term.coefficient.exact_div(&content))?;
+                    }
+                }
+            }
+            None => remainder.push(f.remove(0)),
+        }
+    }
+    primitive(remainder)
+}
+
+/// A reduced Groebner basis of the ideal generated by some polynomials
+#[derive(Clone, Debug)]
+pub struct Basis<C = i64, const N: usize = 4, O = Lex> {
+    polynomials: Vec<Polynomial<C, N>>,
+    order: O,
+}
+
+impl<C, const N: usize, O> Basis<C, N, O>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    O: MonomialOrder,
+{
+    /// Compute the reduced Groebner basis of the ideal generated by `generators` under `order`,
+    /// returning an error if a coefficient or exponent overflows along the way
+    /// ```
+    /// use embedded_algebra::{groebner::Basis, order::Lex, OverflowError, Polynomial};
+    ///
+    /// let generators = [Polynomial::from("a^2 - b"), Polynomial::from("a^3 - a")];
+    /// let basis = Basis::new(&generators, Lex)?;
+    ///
+    /// assert_eq!(
+    ///     basis.polynomials(),
+    ///     &["a^2 - b", "ab - a", "b^2 - b"].map(Polynomial::from)
+    /// );
+    /// # Ok::<(), OverflowError>(())
+    /// ```
+    pub fn new<T>(generators: &[Polynomial<C, N, T>], order: O) -> Result<Self, OverflowError>
+    where
+        T: AsRef<[Monomial<C, N>]>,
+    {
+        let mut basis = Vec::new();
+        for generator in generators {
+            let terms = primitive(normalize(generator.normal().collect(), &order)?)?;
+            if !terms.is_empty() {
+                basis.push(terms);
+            }
+        }
+
+        let mut pairs: Vec<(usize, usize)> = (0..basis.len())
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .collect();
+
+        // Take pairs with the smallest least common multiple of leading terms first
+        while let Some(idx) = (0..pairs.len()).min_by(|&x, &y| {
+            let lcm_of =
+                |(i, j): (usize, usize)| lcm(&basis[i][0].exponents, &basis[j][0].exponents);
+            order.compare(&lcm_of(pairs[x]), &lcm_of(pairs[y]))
+        }) {
+            let (i, j) = pairs.swap_remove(idx);
+            let (f, g) = (&basis[i][0].exponents, &basis[j][0].exponents);
+            let lcm = lcm(f, g);
+
+            // Buchberger's first criterion: coprime leading terms reduce to zero
+            if f.iter().zip(g.iter()).all(|(&a, &b)| a == 0 || b == 0) {
+                continue;
+            }
+
+            // Buchberger's chain criterion
+            let pending = |a: usize, b: usize| pairs.contains(&(a.min(b), a.max(b)));
+            if (0..basis.len()).any(|k| {
+                k != i
+                    && k != j
+                    && divides(&basis[k][0].exponents, &lcm)
+                    && !pending(i, k)
+                    && !pending(j, k)
+            }) {
+                continue;
+            }
+
+            let s = s_polynomial(&basis[i], &basis[j], &order)?;
+            let r = reduce(s, &basis, &order)?;
+            if !r.is_empty() {
+                let k = basis.len();
+                pairs.extend((0..k).map(|i| (i, k)));
+                basis.push(r);
+            }
+        }
+
+        // Keep one polynomial for each minimal leading term
+        let mut minimal: Vec<Terms<C, N>> = Vec::new();
+        for (i, g) in basis.iter().enumerate() {
+            let redundant = basis.iter().enumerate().any(|(j, h)| {
+                j != i
+                    && divides(&h[0].exponents, &g[0].exponents)
+                    && (h[0].exponents != g[0].exponents || j < i)
+            });
+            if !redundant {
+                minimal.push(g.clone());
+            }
+        }
+
+        // Reduce every polynomial by the others
+        for i in 0..minimal.len() {
+            let g = mem::take(&mut minimal[i]);
+            minimal[i] = reduce(g, &minimal, &order)?;
+        }
+        minimal.sort_by(|f, g| order.compare(&g[0].exponents, &f[0].exponents));
+
+        Ok(Self {
+            polynomials: minimal
+                .into_iter()
+                .map(|terms| Polynomial::new(terms.into()))
+                .collect(),
+            order,
+        })
+    }
+
+    /// The polynomials of the basis, each with terms in descending order
+    /// and sorted by descending leading term
+    pub fn polynomials(&self) -> &[Polynomial<C, N>] {
+        &self.polynomials
+    }
+
+    /// The monomial order the basis was computed for, which [`Basis::reduce`] divides by
+    pub fn order(&self) -> &O {
+        &self.order
+    }
+
+    /// Reduce `poly` to its normal form modulo the ideal, which is unique up to a constant factor
+    /// ```
+    /// use embedded_algebra::{groebner::Basis, order::GrevLex, OverflowError, Polynomial};
+    ///
+    /// let generators = [Polynomial::from("ab - 1"), Polynomial::from("b^2 - 2")];
+    /// let basis = Basis::new(&generators, GrevLex)?;
+    ///
+    /// assert_eq!(basis.reduce(&Polynomial::from("a^2b^3"))?, Polynomial::from("b"));
+    /// # Ok::<(), OverflowError>(())
+    /// ```
+    pub fn reduce<T>(&self, poly: &Polynomial<C, N, T>) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        T: AsRef<[Monomial<C, N>]>,
+    {
+        let basis = self
+            .polynomials
+            .iter()
+            .map(|g| g.monomials().to_vec())
+            .collect::<Vec<_>>();
+        let terms = normalize(poly.normal().collect(), &self.order)?;
+        let terms = reduce(terms, &basis, &self.order)?;
+        Ok(Polynomial::new(terms.into()))
+    }
+
+    /// Returns `true` if `poly` belongs to the ideal
+    /// ```
+    /// use embedded_algebra::{groebner::Basis, order::Lex, OverflowError, Polynomial};
+    ///
+    /// let generators = [Polynomial::from("a^2 - b"), Polynomial::from("a^3 - a")];
+    /// let basis = Basis::new(&generators, Lex)?;
+    ///
+    /// assert!(basis.contains(&Polynomial::from("b^3 - b"))?);
+    /// assert!(!basis.contains(&Polynomial::from("a - 1"))?);
+    /// # Ok::<(), OverflowError>(())
+    /// ```
+    pub fn contains<T>(&self, poly: &Polynomial<C, N, T>) -> Result<bool, OverflowError>
+    where
+        T: AsRef<[Monomial<C, N>]>,
+    {
+        self.reduce(poly).map(|poly| poly.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{GrLex, GrevLex};
+
+    /// Check that every S-polynomial reduces to zero and the basis is reduced
+    fn assert_reduced_groebner<O: MonomialOrder>(basis: &Basis<i64, 4, O>) {
+        let order = basis.order();
+        let terms = basis
+            .polynomials()
+            .iter()
+            .map(|g| g.monomials().to_vec())
+            .collect::<Vec<_>>();
+
+        for (i, f) in terms.iter().enumerate() {
+            for g in &terms[i + 1..] {
+                let s = s_polynomial(f, g, order).unwrap();
+                assert!(reduce(s, &terms, order).unwrap().is_empty());
+            }
+            for (j, g) in terms.iter().enumerate() {
+                if i != j {
+                    assert!(f
+                        .iter()
+                        .all(|term| !divides(&g[0].exponents, &term.exponents)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn computes_bases_under_each_order() {
+        let generators = ["a^2 + b^2 + c^2 - 1", "a^2 - b + c^2", "a - c"].map(Polynomial::from);
+
+        let lex = Basis::new(&generators, Lex).unwrap();
+        assert_reduced_groebner(&lex);
+        assert_eq!(
+            lex.polynomials(),
+            &["a - c", "b - 2c^2", "4c^4 + 2c^2 - 1"].map(Polynomial::from)
+        );
+
+        let grlex = Basis::new(&generators, GrLex).unwrap();
+        assert_reduced_groebner(&grlex);
+
+        let grevlex = Basis::new(&generators, GrevLex).unwrap();
+        assert_reduced_groebner(&grevlex);
+
+        for basis in [
+            lex.polynomials(),
+            grlex.polynomials(),
+            grevlex.polynomials(),
+        ]
+        .iter()
+        {
+            for g in basis.iter() {
+                for generator in generators.iter() {
+                    assert!(lex.contains(g).unwrap());
+                    assert!(grevlex.contains(generator).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn handles_trivial_ideals() {
+        let unit = Basis::new(&["2a + 1", "a"].map(Polynomial::from), Lex).unwrap();
+        assert_eq!(unit.polynomials(), &[Polynomial::from("1")]);
+
+        let zero = Basis::new(&[Polynomial::zero()], Lex).unwrap();
+        assert!(zero.polynomials().is_empty());
+        assert!(zero.contains(&Polynomial::zero()).unwrap());
+        assert!(!zero.contains(&Polynomial::from("a")).unwrap());
+    }
+}
@@ -1,20 +1,50 @@
+#![cfg_attr(not(test), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "bigint")]
+mod bigint;
+#[cfg(feature = "bigint")]
+pub use bigint::BigInt;
+
 mod frac;
 pub use frac::Fraction;
 
+pub mod gf2;
+
+#[cfg(feature = "alloc")]
+pub mod groebner;
+
+pub mod order;
+
 pub mod parse;
 
+mod modint;
+pub use modint::ModInt;
+
 mod mono;
-pub use mono::Monomial;
+pub use mono::{CapacityError, Monomial, OverflowError, ParseError};
 
 pub mod poly;
 pub use poly::Polynomial;
 
+mod rational;
+pub use rational::Rational;
+
+pub mod ring;
+
 pub trait Gcd<Rhs = Self> {
     type Output;
 
     fn gcd(&self, rhs: &Rhs) -> Self::Output;
 }
 
+/// Values that can tell whether they are zero, such as the result of a [`Gcd`]
+pub trait Zero {
+    fn is_zero(&self) -> bool;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
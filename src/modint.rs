@@ -0,0 +1,195 @@
+//! Integers modulo a prime, for polynomials over finite fields
+
+use crate::{
+    ring::{ExactDiv, Ring, Saturating},
+    Gcd, Monomial,
+};
+use core::{
+    convert::TryFrom,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// An integer modulo `P`, stored as its least non-negative residue.
+///
+/// `P` should be prime for every nonzero element to have an inverse,
+/// which division and polynomial factorisation rely on.
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use embedded_algebra::{ModInt, Polynomial};
+///
+/// let x = ModInt::<7>::new(3);
+/// assert_eq!(x.pow(6), ModInt::new(1));
+/// assert_eq!(x.inv(), Some(ModInt::new(5)));
+///
+/// let poly: Polynomial<ModInt<7>> = "a^2 + 6 + 1/2a".parse().unwrap();
+/// assert_eq!(poly.to_string(), "1a^2 + 6 + 4a");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModInt<const P: u64>(u64);
+
+impl<const P: u64> ModInt<P> {
+    /// The residue of `value` modulo `P`
+    pub const fn new(value: u64) -> Self {
+        Self(value % P)
+    }
+
+    /// The least non-negative residue
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Raise this to the power `exp` by repeated squaring
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut acc = Self::new(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        acc
+    }
+
+    /// The multiplicative inverse by the extended Euclidean algorithm,
+    /// or `None` if this shares a factor with `P`
+    pub fn inv(self) -> Option<Self> {
+        let (mut r0, mut r1) = (i128::from(P), i128::from(self.0));
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+
+        if r0 == 1 {
+            Some(Self(t0.rem_euclid(i128::from(P)) as u64))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const P: u64> Ring for ModInt<P> {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn one() -> Self {
+        Self::new(1)
+    }
+
+    fn from_i64(n: i64) -> Option<Self> {
+        u64::try_from(i128::from(n).rem_euclid(i128::from(P)))
+            .ok()
+            .map(Self)
+    }
+
+    fn from_ratio(numerator: i64, denominator: i64) -> Option<Self> {
+        Self::from_i64(numerator)?.exact_div(&Self::from_i64(denominator)?)
+    }
+}
+
+/// Arithmetic wraps around the modulus by definition
+impl<const P: u64> Saturating for ModInt<P> {}
+
+impl<const P: u64> ExactDiv for ModInt<P> {
+    fn exact_div(&self, rhs: &Self) -> Option<Self> {
+        Some(*self * rhs.inv()?)
+    }
+}
+
+impl<const P: u64> Gcd for ModInt<P> {
+    type Output = Self;
+
+    /// Every nonzero element of a field is a unit, so this is `1` unless both are zero
+    fn gcd(&self, rhs: &Self) -> Self::Output {
+        if self.0 == 0 && rhs.0 == 0 {
+            Self(0)
+        } else {
+            Self::new(1)
+        }
+    }
+}
+
+impl<const P: u64> Add for ModInt<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(((u128::from(self.0) + u128::from(rhs.0)) % u128::from(P)) as u64)
+    }
+}
+
+impl<const P: u64> Sub for ModInt<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const P: u64> Mul for ModInt<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(((u128::from(self.0) * u128::from(rhs.0)) % u128::from(P)) as u64)
+    }
+}
+
+impl<const P: u64> Neg for ModInt<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(P - self.0)
+    }
+}
+
+impl<const P: u64> From<u64> for ModInt<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const P: u64, const N: usize> From<ModInt<P>> for Monomial<ModInt<P>, N> {
+    fn from(coefficient: ModInt<P>) -> Self {
+        Self::coefficient(coefficient)
+    }
+}
+
+impl<const P: u64> fmt::Display for ModInt<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModInt;
+    use crate::ring::Ring;
+
+    #[test]
+    fn wraps_at_the_modulus() {
+        const P: u64 = u64::MAX - 58;
+        let x = ModInt::<P>::new(P - 1);
+
+        assert_eq!(x + ModInt::new(2), ModInt::new(1));
+        assert_eq!(x * x, ModInt::new(1));
+        assert_eq!(-ModInt::<P>::zero(), ModInt::zero());
+        assert_eq!(ModInt::<P>::from_i64(-1), Some(x));
+        assert_eq!(ModInt::<P>::new(12345).pow(P - 1), ModInt::one());
+    }
+
+    #[test]
+    fn inverts_units() {
+        for value in 1..13 {
+            let x = ModInt::<13>::new(value);
+            assert_eq!(x * x.inv().unwrap(), ModInt::one());
+        }
+        assert_eq!(ModInt::<13>::zero().inv(), None);
+        assert_eq!(ModInt::<12>::new(4).inv(), None);
+        assert_eq!(ModInt::<7>::from_ratio(3, 7), None);
+    }
+}
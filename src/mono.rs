@@ -1,27 +1,31 @@
-use crate::{Gcd, Polynomial};
-use std::{
+use crate::{
+    parse::{Error, Expected, Item, Items, Parser, Span},
+    ring::{ExactDiv, Ring, Saturating},
+    Gcd, Polynomial, Zero,
+};
+use core::{
     fmt::{self},
     iter::Product,
-    ops::{DivAssign, Mul, MulAssign},
-    str::{Chars, FromStr},
+    ops::{DivAssign, Mul, MulAssign, Neg},
+    str::FromStr,
 };
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Monomial {
-    pub coefficient: i64,
-    pub exponents: [u16; 4],
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Monomial<C = i64, const N: usize = 4> {
+    pub coefficient: C,
+    pub exponents: [u16; N],
 }
 
-impl Monomial {
-    pub const fn new(coefficient: i64, exponents: [u16; 4]) -> Self {
+impl<C, const N: usize> Monomial<C, N> {
+    pub const fn new(coefficient: C, exponents: [u16; N]) -> Self {
         Self {
             coefficient,
             exponents,
         }
     }
 
-    pub const fn coefficient(coefficient: i64) -> Self {
-        Self::new(coefficient, [0; 4])
+    pub const fn coefficient(coefficient: C) -> Self {
+        Self::new(coefficient, [0; N])
     }
 
     pub fn exponent(mut self, index: usize, degree: u16) -> Self {
@@ -29,6 +33,97 @@ impl Monomial {
         self
     }
 
+    /// Multiply this by another monomial, returning an error instead of overflowing
+    /// ```
+    /// use embedded_algebra::{Monomial, OverflowError};
+    ///
+    /// let a = Monomial::from("3a");
+    ///
+    /// assert_eq!(a.checked_mul(Monomial::from("2ab")), Ok(Monomial::from("6a^2b")));
+    /// assert_eq!(
+    ///     a.checked_mul(Monomial::coefficient(i64::MAX)),
+    ///     Err(OverflowError::Coefficient)
+    /// );
+    /// ```
+    pub fn checked_mul(mut self, rhs: Self) -> Result<Self, OverflowError>
+    where
+        C: Ring,
+    {
+        self.coefficient = self
+            .coefficient
+            .checked_mul(&rhs.coefficient)
+            .ok_or(OverflowError::Coefficient)?;
+        for (dest, src) in self.exponents.iter_mut().zip(rhs.exponents.iter()) {
+            *dest = dest.checked_add(*src).ok_or(OverflowError::Exponent)?;
+        }
+        Ok(self)
+    }
+
+    /// Multiply this by another monomial, clamping the coefficient and exponents
+    /// to their bounds instead of overflowing
+    /// ```
+    /// use embedded_algebra::Monomial;
+    ///
+    /// let a = Monomial::new(3, [u16::MAX, 1, 0, 0]);
+    ///
+    /// assert_eq!(
+    ///     a.saturating_mul(Monomial::new(i64::MAX, [1, 1, 0, 0])),
+    ///     Monomial::new(i64::MAX, [u16::MAX, 2, 0, 0])
+    /// );
+    /// ```
+    pub fn saturating_mul(mut self, rhs: Self) -> Self
+    where
+        C: Saturating,
+    {
+        self.coefficient = self.coefficient.saturating_mul(&rhs.coefficient);
+        for (dest, src) in self.exponents.iter_mut().zip(rhs.exponents.iter()) {
+            *dest = dest.saturating_add(*src);
+        }
+        self
+    }
+
+    /// Negate this, returning an error instead of overflowing
+    pub fn checked_neg(mut self) -> Result<Self, OverflowError>
+    where
+        C: Ring,
+    {
+        self.coefficient = self
+            .coefficient
+            .checked_neg()
+            .ok_or(OverflowError::Coefficient)?;
+        Ok(self)
+    }
+
+    /// Negate this, clamping the coefficient to its bounds instead of overflowing
+    pub fn saturating_neg(mut self) -> Self
+    where
+        C: Saturating,
+    {
+        self.coefficient = self.coefficient.saturating_neg();
+        self
+    }
+
+    /// Divide this by another monomial, returning `None` unless `rhs` divides it exactly
+    /// ```
+    /// use embedded_algebra::Monomial;
+    ///
+    /// let a = Monomial::from("6a^2b");
+    ///
+    /// assert_eq!(a.checked_div(Monomial::from("3a")), Some(Monomial::from("2ab")));
+    /// assert_eq!(a.checked_div(Monomial::from("4a")), None);
+    /// assert_eq!(a.checked_div(Monomial::from("c")), None);
+    /// ```
+    pub fn checked_div(mut self, rhs: Self) -> Option<Self>
+    where
+        C: ExactDiv,
+    {
+        self.coefficient = self.coefficient.exact_div(&rhs.coefficient)?;
+        for (dest, src) in self.exponents.iter_mut().zip(rhs.exponents.iter()) {
+            *dest = dest.checked_sub(*src)?;
+        }
+        Some(self)
+    }
+
     /// Compute the greatest common divisor for this and another monomial
     /// ```
     /// use embedded_algebra::Monomial;
@@ -38,18 +133,11 @@ impl Monomial {
     ///
     /// assert_eq!(a.gcd(b), Monomial::from("2a"));
     ///
-    pub fn gcd(mut self, rhs: Self) -> Self {
-        #[inline]
-        fn gcd(mut m: i64, mut n: i64) -> i64 {
-            while m != 0 {
-                let old_m = m;
-                m = n % m;
-                n = old_m;
-            }
-            n.abs()
-        }
-
-        self.coefficient = gcd(self.coefficient, rhs.coefficient);
+    pub fn gcd(mut self, rhs: Self) -> Self
+    where
+        C: Gcd<Output = C>,
+    {
+        self.coefficient = self.coefficient.gcd(&rhs.coefficient);
         for (a, b) in self.exponents.iter_mut().zip(rhs.exponents.iter().copied()) {
             *a = b.min(*a)
         }
@@ -57,71 +145,90 @@ impl Monomial {
     }
 }
 
-impl Default for Monomial {
+impl<C, const N: usize> Default for Monomial<C, N>
+where
+    C: Ring,
+{
     fn default() -> Self {
-        Self::coefficient(1)
+        Self::coefficient(C::one())
     }
 }
 
-impl From<i64> for Monomial {
-    fn from(coefficient: i64) -> Self {
-        Self::coefficient(coefficient)
-    }
+macro_rules! impl_from_coefficient {
+    ($($t:ty),*) => {
+        $(
+            impl<const N: usize> From<$t> for Monomial<$t, N> {
+                fn from(coefficient: $t) -> Self {
+                    Self::coefficient(coefficient)
+                }
+            }
+        )*
+    };
 }
 
-impl DivAssign for Monomial {
+impl_from_coefficient!(i8, i16, i32, i64, i128, f32, f64);
+
+impl<C, const N: usize> DivAssign for Monomial<C, N>
+where
+    C: ExactDiv,
+{
+    /// # Panics
+    /// If `rhs` does not divide this monomial exactly, see [`Monomial::checked_div`]
     fn div_assign(&mut self, rhs: Self) {
-        self.coefficient /= rhs.coefficient;
-        for (dest, src) in self.exponents.iter_mut().zip(rhs.exponents.iter()) {
-            *dest -= src;
-        }
+        *self = self
+            .clone()
+            .checked_div(rhs)
+            .expect("monomial is not divisible by the divisor");
     }
 }
 
-impl MulAssign for Monomial {
+impl<C, const N: usize> MulAssign for Monomial<C, N>
+where
+    C: Ring,
+{
     fn mul_assign(&mut self, rhs: Self) {
-        self.coefficient *= rhs.coefficient;
+        self.coefficient = self.coefficient.clone() * rhs.coefficient;
         for (dest, src) in self.exponents.iter_mut().zip(rhs.exponents.iter()) {
             *dest += src;
         }
     }
 }
 
-impl Gcd for Monomial {
+impl<C, const N: usize> Gcd for Monomial<C, N>
+where
+    C: Gcd<Output = C> + Clone,
+{
     type Output = Self;
 
     fn gcd(&self, rhs: &Self) -> Self::Output {
-        #[inline]
-        fn gcd(mut m: i64, mut n: i64) -> i64 {
-            while m != 0 {
-                let old_m = m;
-                m = n % m;
-                n = old_m;
-            }
-            n.abs()
-        }
+        self.clone().gcd(rhs.clone())
+    }
+}
 
-        let mut me = *self;
-        me.coefficient = gcd(self.coefficient, rhs.coefficient);
-        for (a, b) in me.exponents.iter_mut().zip(rhs.exponents.iter().copied()) {
-            *a = b.min(*a)
-        }
-        me
+impl<C, const N: usize> Zero for Monomial<C, N>
+where
+    C: Ring,
+{
+    fn is_zero(&self) -> bool {
+        self.coefficient.is_zero()
     }
 }
 
-impl<T> Gcd<Polynomial<T>> for Monomial
+impl<C, const N: usize, T> Gcd<Polynomial<C, N, T>> for Monomial<C, N>
 where
-    T: AsRef<[Monomial]>,
+    Polynomial<C, N, T>: Gcd<Self, Output = Self>,
 {
     type Output = Self;
 
-    fn gcd(&self, rhs: &Polynomial<T>) -> Self::Output {
+    fn gcd(&self, rhs: &Polynomial<C, N, T>) -> Self::Output {
         rhs.gcd(self)
     }
 }
 
-impl Mul for Monomial {
+impl<C, const N: usize> Mul for Monomial<C, N>
+where
+    C: Ring,
+{
     type Output = Self;
 
     fn mul(mut self, rhs: Self) -> Self::Output {
@@ -130,13 +237,30 @@ impl Mul for Monomial {
     }
 }
 
-impl Product for Monomial {
+impl<C, const N: usize> Neg for Monomial<C, N>
+where
+    C: Neg<Output = C>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.coefficient, self.exponents)
+    }
+}
+
+impl<C, const N: usize> Product for Monomial<C, N>
+where
+    C: Ring,
+{
     fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Monomial::default(), |acc, monomial| acc * monomial)
     }
 }
 
-impl fmt::Display for Monomial {
+impl<C, const N: usize> fmt::Display for Monomial<C, N>
+where
+    C: fmt::Display,
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.coefficient)?;
 
@@ -159,102 +283,119 @@ impl From<&str> for Monomial {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+/// A polynomial needed more terms than its fixed-capacity storage can hold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("capacity exceeded")
+    }
+}
+
+/// An operation left the range of a coefficient or exponent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OverflowError {
     Coefficient,
     Exponent,
+    /// An exponent would drop below zero, as when dividing by a monomial of higher degree
+    Underflow,
 }
 
-#[derive(Clone, Copy, Debug)]
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OverflowError::Coefficient => "coefficient overflow",
+            OverflowError::Exponent => "exponent overflow",
+            OverflowError::Underflow => "exponent underflow",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ParseError {
     Empty,
     Overflow(OverflowError),
+    /// A fractional coefficient that the coefficient type cannot represent
+    Coefficient,
     Symbol,
+    /// A variable beyond the number a monomial can hold
+    Variable,
+    /// Groups or signs are nested too deeply
+    Depth,
+    /// More terms than the output can hold
+    Capacity,
+    Unexpected {
+        expected: Expected,
+        /// The unexpected item, or `None` at the end of the input
+        found: Option<Item>,
+    },
 }
 
-impl FromStr for Monomial {
-    type Err = ParseError;
-
-    #[inline]
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut chars = s.chars();
-        let mut pos = 0;
-        let mut mono = Self::default();
-
-        match chars.next() {
-            Some('0'..='9') => {
-                pos += 1;
-                let (coeff, next) = parse_int(s, &mut chars, 0, &mut pos)
-                    .ok_or(ParseError::Overflow(OverflowError::Coefficient))?;
-                mono.coefficient = coeff;
-                match next {
-                    Some(c) => {
-                        return parse_vars(s, &mut chars, &mut pos, c, &mut mono).map(|()| mono)
-                    }
-                    None => Ok(mono),
-                }
-            }
-            Some(c) => parse_vars(s, &mut chars, &mut pos, c, &mut mono).map(|()| mono),
-            None => Err(ParseError::Empty),
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => f.write_str("empty input"),
+            ParseError::Overflow(overflow) => overflow.fmt(f),
+            ParseError::Coefficient => f.write_str("unrepresentable coefficient"),
+            ParseError::Symbol => f.write_str("unknown symbol"),
+            ParseError::Variable => f.write_str("variable out of range"),
+            ParseError::Depth => f.write_str("nesting too deep"),
+            ParseError::Capacity => CapacityError.fmt(f),
+            ParseError::Unexpected {
+                expected,
+                found: Some(found),
+            } => write!(f, "expected {}, found {}", expected, found),
+            ParseError::Unexpected {
+                expected,
+                found: None,
+            } => write!(f, "expected {}, found end of input", expected),
         }
     }
 }
 
-#[inline]
-fn parse_vars(
-    s: &str,
-    chars: &mut Chars,
-    pos: &mut usize,
-    c: char,
-    mono: &mut Monomial,
-) -> Result<(), ParseError> {
-    let mut c = c;
-    loop {
-        let idx = (c as u8 - 97) as usize;
-        if idx > 4 {
-            return Err(ParseError::Symbol);
-        }
-        match chars.next() {
-            Some('^') => {
-                *pos += 2;
-                let (exp, next) = parse_int(s, chars, *pos, pos)
-                    .ok_or(ParseError::Overflow(OverflowError::Exponent))?;
-                mono.exponents[idx] = exp as _;
-                if let Some(next) = next {
-                    c = next;
-                } else {
-                    break;
-                }
-            }
-            Some(c2) => {
-                *pos += 1;
-                mono.exponents[idx] = 1;
-                c = c2;
-            }
-            None => {
-                mono.exponents[idx] = 1;
-                break;
-            }
-        }
-    }
-    Ok(())
-}
+impl<C, const N: usize> FromStr for Monomial<C, N>
+where
+    C: Ring,
+{
+    type Err = Error;
 
-#[inline]
-fn parse_int(
-    s: &str,
-    chars: &mut Chars,
-    start: usize,
-    pos: &mut usize,
-) -> Option<(i64, Option<char>)> {
-    let next = loop {
-        match chars.next() {
-            Some('0'..='9') => {
-                *pos += 1;
-            }
-            next => break next,
+    /// Parse a single term, reporting where the input is malformed
+    /// ```
+    /// use embedded_algebra::{
+    ///     parse::{Expected, Item, Operator, Span},
+    ///     Monomial, ParseError,
+    /// };
+    ///
+    /// assert_eq!("-3a^2".parse(), Ok(Monomial::new(-3, [2, 0, 0, 0])));
+    /// assert_eq!("".parse::<Monomial>().unwrap_err().kind, ParseError::Empty);
+    ///
+    /// let error = "a - b".parse::<Monomial>().unwrap_err();
+    /// assert_eq!(error.span, Span::new(2, 3));
+    /// assert_eq!(
+    ///     error.kind,
+    ///     ParseError::Unexpected {
+    ///         expected: Expected::End,
+    ///         found: Some(Item::Operator(Operator::Sub)),
+    ///     }
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let mono = parser
+            .next()
+            .ok_or(Error::new(ParseError::Empty, Span::new(0, s.len())))??;
+
+        // A term only ends at the end of the input or at the operator that
+        // the parser has already consumed, so read that operator again
+        let end = parser.span().end;
+        match Items::new(&s[end..]).next() {
+            Some(Ok(token)) => Err(Error::unexpected(
+                Expected::End,
+                Some(token.item),
+                Span::new(end + token.span.start, end + token.span.end),
+            )),
+            _ => Ok(mono),
         }
-    };
-    let int_str = &s[start..*pos];
-    int_str.parse().ok().map(|int| (int, next))
+    }
 }
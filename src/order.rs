@@ -0,0 +1,71 @@
+use core::cmp::Ordering;
+
+/// A total order on monomials by their exponents, where `a > b > c > d`
+pub trait MonomialOrder {
+    fn compare(&self, lhs: &[u16], rhs: &[u16]) -> Ordering;
+}
+
+impl<O> MonomialOrder for &O
+where
+    O: MonomialOrder + ?Sized,
+{
+    fn compare(&self, lhs: &[u16], rhs: &[u16]) -> Ordering {
+        (**self).compare(lhs, rhs)
+    }
+}
+
+fn degree(exponents: &[u16]) -> u32 {
+    exponents.iter().map(|&exp| u32::from(exp)).sum()
+}
+
+/// Lexicographic order, comparing exponents of `a` first
+/// ```
+/// use embedded_algebra::order::{Lex, MonomialOrder};
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Lex.compare(&[1, 0, 0, 0], &[0, 3, 0, 0]), Ordering::Greater);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lex;
+
+impl MonomialOrder for Lex {
+    fn compare(&self, lhs: &[u16], rhs: &[u16]) -> Ordering {
+        lhs.cmp(rhs)
+    }
+}
+
+/// Graded lexicographic order, comparing total degree before [`Lex`]
+/// ```
+/// use embedded_algebra::order::{GrLex, MonomialOrder};
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(GrLex.compare(&[1, 0, 0, 0], &[0, 3, 0, 0]), Ordering::Less);
+/// assert_eq!(GrLex.compare(&[1, 0, 2, 0], &[0, 3, 0, 0]), Ordering::Greater);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GrLex;
+
+impl MonomialOrder for GrLex {
+    fn compare(&self, lhs: &[u16], rhs: &[u16]) -> Ordering {
+        degree(lhs).cmp(&degree(rhs)).then_with(|| lhs.cmp(rhs))
+    }
+}
+
+/// Graded reverse lexicographic order, comparing total degree and then
+/// preferring the smaller exponent of the last variable that differs
+/// ```
+/// use embedded_algebra::order::{GrevLex, MonomialOrder};
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(GrevLex.compare(&[1, 0, 2, 0], &[0, 3, 0, 0]), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GrevLex;
+
+impl MonomialOrder for GrevLex {
+    fn compare(&self, lhs: &[u16], rhs: &[u16]) -> Ordering {
+        degree(lhs)
+            .cmp(&degree(rhs))
+            .then_with(|| rhs.iter().rev().cmp(lhs.iter().rev()))
+    }
+}
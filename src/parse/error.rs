@@ -0,0 +1,110 @@
+use super::Item;
+use crate::ParseError;
+use core::fmt;
+
+/// A range of byte offsets into the source string
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// What the parser was looking for when it found an unexpected item
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expected {
+    /// A coefficient, variable or unary minus
+    Term,
+    /// An integer following `^`
+    Exponent,
+    /// A `+` or `-` between terms
+    Operator,
+    /// A `)` closing a group
+    Close,
+    /// The end of the input
+    End,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Expected::Term => "a coefficient or variable",
+            Expected::Exponent => "an exponent",
+            Expected::Operator => "`+` or `-`",
+            Expected::Close => "`)`",
+            Expected::End => "end of input",
+        })
+    }
+}
+
+/// An error encountered while parsing, along with where it occurred
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ParseError,
+    pub span: Span,
+}
+
+impl Error {
+    pub const fn new(kind: ParseError, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub(crate) const fn unexpected(expected: Expected, found: Option<Item>, span: Span) -> Self {
+        Self::new(ParseError::Unexpected { expected, found }, span)
+    }
+
+    /// Render this error against the source it came from, pointing at the offending input
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let s = "2a^ + b";
+    /// let error = s.parse::<Polynomial>().unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.diagnostic(s).to_string(),
+    ///     "2a^ + b\n    ^ expected an exponent, found `+`"
+    /// );
+    /// # }
+    /// ```
+    pub fn diagnostic<'a>(&'a self, source: &'a str) -> Diagnostic<'a> {
+        Diagnostic {
+            error: self,
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind, self.span.start)
+    }
+}
+
+/// Caret-style rendering of an [`Error`] created by [`Error::diagnostic`]
+pub struct Diagnostic<'a> {
+    error: &'a Error,
+    source: &'a str,
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.error.span;
+        let start = span.start.min(self.source.len());
+        let end = span.end.clamp(start, self.source.len());
+
+        writeln!(f, "{}", self.source)?;
+        for _ in self.source[..start].chars() {
+            f.write_str(" ")?;
+        }
+        for _ in 0..self.source[start..end].chars().count().max(1) {
+            f.write_str("^")?;
+        }
+        write!(f, " {}", self.error.kind)
+    }
+}
@@ -0,0 +1,498 @@
+use super::{Error, Expected, Item, Items, Operator, Span, Token};
+use crate::{ring::Ring, Monomial, OverflowError, ParseError, Polynomial};
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+
+/// The deepest nesting of groups and unary minus signs accepted by [`expand`]
+pub const MAX_DEPTH: usize = 32;
+
+/// The most terms any subexpression may expand to in [`expand`],
+/// which bounds the work a short input such as `(a + b + c + d)^120` can cause
+pub const MAX_TERMS: usize = 4096;
+
+/// Parse an expression with groups, explicit `*` or implicit multiplication and integer powers,
+/// expanding it into a polynomial with like terms combined
+/// ```
+/// use embedded_algebra::{parse, Polynomial};
+///
+/// let poly = parse::expand("(a + 1)(a - 1)").unwrap();
+/// assert_eq!(poly, Polynomial::from("a^2 - 1"));
+///
+/// let poly = parse::expand("2(a + b)^2 - 4ab").unwrap();
+/// assert_eq!(poly, Polynomial::from("2a^2 + 2b^2"));
+/// ```
+pub fn expand<C, const N: usize>(s: &str) -> Result<Polynomial<C, N>, Error>
+where
+    C: Ring,
+{
+    let mut expander = Expander {
+        items: Items::new(s),
+        peeked: None,
+        len: s.len(),
+        depth: 0,
+    };
+
+    if expander.peek()?.is_none() {
+        return Ok(Polynomial::zero());
+    }
+
+    let expr = expander.expr()?;
+    match expander.next()? {
+        None => Ok(Polynomial::new(expr.terms.terms.into())),
+        Some(token) => Err(Error::unexpected(
+            Expected::Operator,
+            Some(token.item),
+            token.span,
+        )),
+    }
+}
+
+/// Combined terms in the order they first appeared,
+/// with their positions sorted by exponents to find like terms quickly
+struct Terms<C, const N: usize> {
+    terms: Vec<Monomial<C, N>>,
+    sorted: Vec<usize>,
+}
+
+impl<C, const N: usize> Terms<C, N>
+where
+    C: Ring,
+{
+    fn new() -> Self {
+        Self {
+            terms: Vec::new(),
+            sorted: Vec::new(),
+        }
+    }
+
+    fn single(term: Monomial<C, N>) -> Self {
+        if term.coefficient.is_zero() {
+            return Self::new();
+        }
+        Self {
+            terms: vec![term],
+            sorted: vec![0],
+        }
+    }
+
+    /// Add a term, removing its like term if it cancels out
+    fn push(&mut self, term: Monomial<C, N>) -> Result<(), ParseError> {
+        let terms = &self.terms;
+        match self
+            .sorted
+            .binary_search_by(|&pos| terms[pos].exponents.cmp(&term.exponents))
+        {
+            Ok(idx) => {
+                let pos = self.sorted[idx];
+                let coefficient = self.terms[pos]
+                    .coefficient
+                    .checked_add(&term.coefficient)
+                    .ok_or(ParseError::Overflow(OverflowError::Coefficient))?;
+
+                if coefficient.is_zero() {
+                    self.terms.remove(pos);
+                    self.sorted.remove(idx);
+                    for later in self.sorted.iter_mut().filter(|later| **later > pos) {
+                        *later -= 1;
+                    }
+                } else {
+                    self.terms[pos].coefficient = coefficient;
+                }
+            }
+            Err(_) if term.coefficient.is_zero() => {}
+            Err(_) if self.terms.len() == MAX_TERMS => return Err(ParseError::Capacity),
+            Err(idx) => {
+                self.sorted.insert(idx, self.terms.len());
+                self.terms.push(term);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn product<C, const N: usize>(
+    lhs: &[Monomial<C, N>],
+    rhs: &[Monomial<C, N>],
+) -> Result<Terms<C, N>, ParseError>
+where
+    C: Ring,
+{
+    let mut terms = Terms::new();
+    for a in lhs {
+        for b in rhs {
+            let term = a
+                .clone()
+                .checked_mul(b.clone())
+                .map_err(ParseError::Overflow)?;
+            terms.push(term)?;
+        }
+    }
+    Ok(terms)
+}
+
+/// A fully expanded subexpression and the input it came from
+struct Expr<C, const N: usize> {
+    terms: Terms<C, N>,
+    span: Span,
+}
+
+impl<C, const N: usize> Expr<C, N>
+where
+    C: Ring,
+{
+    fn new(monomial: Monomial<C, N>, span: Span) -> Self {
+        Self {
+            terms: Terms::single(monomial),
+            span,
+        }
+    }
+
+    fn add(&mut self, rhs: Self, negate: bool) -> Result<(), Error> {
+        let span = Span::new(self.span.start, rhs.span.end);
+        for mut term in rhs.terms.terms {
+            if negate {
+                term.coefficient = term.coefficient.checked_neg().ok_or(Error::new(
+                    ParseError::Overflow(OverflowError::Coefficient),
+                    rhs.span,
+                ))?;
+            }
+            self.terms
+                .push(term)
+                .map_err(|kind| Error::new(kind, span))?;
+        }
+        self.span = span;
+        Ok(())
+    }
+
+    fn mul(self, rhs: &Self) -> Result<Self, Error> {
+        let span = Span::new(self.span.start, rhs.span.end);
+        let terms =
+            product(&self.terms.terms, &rhs.terms.terms).map_err(|kind| Error::new(kind, span))?;
+        Ok(Self { terms, span })
+    }
+
+    fn pow(self, mut exp: u16, span: Span) -> Result<Self, Error> {
+        let span = Span::new(self.span.start, span.end);
+        let error = |kind| Error::new(kind, span);
+
+        let mut terms = Terms::single(Monomial::default());
+        let mut base = self.terms;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                terms = product(&terms.terms, &base.terms).map_err(error)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = product(&base.terms, &base.terms).map_err(error)?;
+            }
+        }
+        Ok(Self { terms, span })
+    }
+
+    fn neg(mut self, span: Span) -> Result<Self, Error> {
+        self.span = Span::new(span.start, self.span.end);
+        for term in self.terms.terms.iter_mut() {
+            term.coefficient = term.coefficient.checked_neg().ok_or(Error::new(
+                ParseError::Overflow(OverflowError::Coefficient),
+                self.span,
+            ))?;
+        }
+        Ok(self)
+    }
+}
+
+/// Recursive descent parser for
+/// ```text
+/// expr   = term (("+" | "-") term)*
+/// term   = factor ("*"? factor)*
+/// factor = "-" factor | atom ("^" integer)?
+/// atom   = integer | ratio | variable | "(" expr ")"
+/// ```
+/// where implicit multiplication requires the next factor to start with a variable or group
+struct Expander<'a> {
+    items: Items<'a>,
+    peeked: Option<Token>,
+    len: usize,
+    depth: usize,
+}
+
+impl Expander<'_> {
+    fn peek(&mut self) -> Result<Option<Token>, Error> {
+        if self.peeked.is_none() {
+            self.peeked = self.items.next().transpose()?;
+        }
+        Ok(self.peeked)
+    }
+
+    fn peek_item(&mut self) -> Result<Option<Item>, Error> {
+        self.peek().map(|token| token.map(|token| token.item))
+    }
+
+    fn next(&mut self) -> Result<Option<Token>, Error> {
+        let token = self.peek()?;
+        self.peeked = None;
+        Ok(token)
+    }
+
+    fn end(&self) -> Span {
+        Span::new(self.len, self.len)
+    }
+
+    fn nested<C, F, const N: usize>(&mut self, span: Span, f: F) -> Result<Expr<C, N>, Error>
+    where
+        F: FnOnce(&mut Self) -> Result<Expr<C, N>, Error>,
+    {
+        if self.depth == MAX_DEPTH {
+            return Err(Error::new(ParseError::Depth, span));
+        }
+
+        self.depth += 1;
+        let expr = f(self);
+        self.depth -= 1;
+        expr
+    }
+
+    fn expr<C, const N: usize>(&mut self) -> Result<Expr<C, N>, Error>
+    where
+        C: Ring,
+    {
+        let mut sum = self.term()?;
+        loop {
+            let negate = match self.peek_item()? {
+                Some(Item::Operator(Operator::Add)) => false,
+                Some(Item::Operator(Operator::Sub)) => true,
+                _ => break Ok(sum),
+            };
+            self.next()?;
+
+            let term = self.term()?;
+            sum.add(term, negate)?;
+        }
+    }
+
+    fn term<C, const N: usize>(&mut self) -> Result<Expr<C, N>, Error>
+    where
+        C: Ring,
+    {
+        let mut product = self.factor()?;
+        loop {
+            match self.peek_item()? {
+                Some(Item::Operator(Operator::Mul)) => {
+                    self.next()?;
+                }
+                Some(Item::Variable(_)) | Some(Item::Open) => {}
+                _ => break Ok(product),
+            }
+
+            let factor = self.factor()?;
+            product = product.mul(&factor)?;
+        }
+    }
+
+    fn factor<C, const N: usize>(&mut self) -> Result<Expr<C, N>, Error>
+    where
+        C: Ring,
+    {
+        let token = match self.next()? {
+            Some(token) => token,
+            None => return Err(Error::unexpected(Expected::Term, None, self.end())),
+        };
+
+        let base = match token.item {
+            Item::Operator(Operator::Sub) => {
+                return self.nested(token.span, Self::factor)?.neg(token.span);
+            }
+            Item::Integer(int) => {
+                let coefficient = super::coefficient(int, 1, token.span)?;
+                Expr::new(Monomial::coefficient(coefficient), token.span)
+            }
+            Item::Large => {
+                let coefficient = super::large(self.items.slice(token.span), token.span)?;
+                Expr::new(Monomial::coefficient(coefficient), token.span)
+            }
+            Item::Ratio(num, den) => {
+                let coefficient = super::coefficient(num, den, token.span)?;
+                Expr::new(Monomial::coefficient(coefficient), token.span)
+            }
+            Item::Variable(var) if usize::from(var) < N => {
+                Expr::new(Monomial::default().exponent(var as usize, 1), token.span)
+            }
+            Item::Variable(_) => return Err(Error::new(ParseError::Variable, token.span)),
+            Item::Open => {
+                let mut inner = self.nested(token.span, Self::expr)?;
+                match self.next()? {
+                    Some(Token {
+                        item: Item::Close,
+                        span,
+                    }) => inner.span = Span::new(token.span.start, span.end),
+                    Some(found) => {
+                        return Err(Error::unexpected(
+                            Expected::Close,
+                            Some(found.item),
+                            found.span,
+                        ))
+                    }
+                    None => return Err(Error::unexpected(Expected::Close, None, self.end())),
+                }
+                inner
+            }
+            found => return Err(Error::unexpected(Expected::Term, Some(found), token.span)),
+        };
+
+        if let Some(Item::Operator(Operator::Pow)) = self.peek_item()? {
+            self.next()?;
+            let (exp, span) = self.exponent()?;
+            base.pow(exp, span)
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn exponent(&mut self) -> Result<(u16, Span), Error> {
+        let overflow = |span| Error::new(ParseError::Overflow(OverflowError::Exponent), span);
+
+        // Integers are only known to be exponents by their position
+        let token = self.next().map_err(|error| match error.kind {
+            ParseError::Overflow(_) => overflow(error.span),
+            _ => error,
+        })?;
+
+        match token {
+            Some(Token {
+                item: Item::Integer(exp),
+                span,
+            }) => u16::try_from(exp)
+                .map(|exp| (exp, span))
+                .map_err(|_| overflow(span)),
+            Some(Token {
+                item: Item::Large,
+                span,
+            }) => Err(overflow(span)),
+            Some(found) => Err(Error::unexpected(
+                Expected::Exponent,
+                Some(found.item),
+                found.span,
+            )),
+            None => Err(Error::unexpected(Expected::Exponent, None, self.end())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ModInt;
+
+    #[test]
+    fn expands_products_of_groups() {
+        assert_eq!(
+            expand::<i64, 4>("(a + 1)(a + 1)").unwrap(),
+            Polynomial::from("a^2 + 2a + 1")
+        );
+        assert_eq!(
+            expand::<i64, 4>("(a - b) * (a + b)").unwrap(),
+            Polynomial::from("a^2 - b^2")
+        );
+    }
+
+    #[test]
+    fn expands_powers_of_groups() {
+        assert_eq!(
+            expand::<i64, 4>("2(a + b)^3").unwrap().into_canonical(),
+            Polynomial::from("2a^3 + 6a^2b + 6ab^2 + 2b^3")
+        );
+        assert_eq!(
+            expand::<i64, 4>("(a + b)^0").unwrap(),
+            Polynomial::from("1")
+        );
+        assert_eq!(expand::<i64, 4>("2^3a").unwrap(), Polynomial::from("8a"));
+    }
+
+    #[test]
+    fn expands_nested_groups() {
+        assert_eq!(
+            expand::<i64, 4>("-(a(b - (c + 1)))").unwrap(),
+            Polynomial::from("-ab + ac + a")
+        );
+        assert_eq!(expand::<i64, 4>("-a^2").unwrap(), Polynomial::from("-a^2"));
+        assert_eq!(expand::<i64, 4>("((a))-a").unwrap(), Polynomial::zero());
+    }
+
+    #[test]
+    fn expands_other_coefficients() {
+        assert_eq!(
+            expand::<f64, 4>("(a - 1)^2").unwrap().to_string(),
+            "1a^2 - 2a + 1"
+        );
+        assert_eq!(expand::<i8, 4>("(a + 1)^9").unwrap().monomials().len(), 10);
+        assert_eq!(
+            expand::<i8, 4>("(a + 1)^10").unwrap_err().kind,
+            ParseError::Overflow(OverflowError::Coefficient)
+        );
+        assert_eq!(
+            expand::<i8, 4>("a + 200").unwrap_err(),
+            Error::new(
+                ParseError::Overflow(OverflowError::Coefficient),
+                Span::new(4, 7)
+            )
+        );
+    }
+
+    #[test]
+    fn reports_unclosed_groups() {
+        assert_eq!(
+            expand::<i64, 4>("(a + 1").unwrap_err(),
+            Error::unexpected(Expected::Close, None, Span::new(6, 6))
+        );
+        assert_eq!(
+            expand::<i64, 4>("(a 2)").unwrap_err(),
+            Error::unexpected(Expected::Close, Some(Item::Integer(2)), Span::new(3, 4))
+        );
+        assert_eq!(
+            expand::<i64, 4>("a)").unwrap_err(),
+            Error::unexpected(Expected::Operator, Some(Item::Close), Span::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn reports_overflow() {
+        assert_eq!(
+            expand::<i64, 4>("(a + 1)^100").unwrap_err(),
+            Error::new(
+                ParseError::Overflow(OverflowError::Coefficient),
+                Span::new(0, 11)
+            )
+        );
+        assert_eq!(
+            expand::<i64, 4>("(a^40000)^2").unwrap_err(),
+            Error::new(
+                ParseError::Overflow(OverflowError::Exponent),
+                Span::new(0, 11)
+            )
+        );
+    }
+
+    #[test]
+    fn limits_terms() {
+        assert_eq!(
+            expand::<ModInt<7>, 4>("(a+b+c+d)^120").unwrap_err(),
+            Error::new(ParseError::Capacity, Span::new(0, 13))
+        );
+        assert_eq!(
+            expand::<f64, 4>("(a+b+c+d)^20").unwrap().monomials().len(),
+            1771
+        );
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let s = "(".repeat(MAX_DEPTH + 1);
+        assert_eq!(
+            expand::<i64, 4>(&s).unwrap_err(),
+            Error::new(ParseError::Depth, Span::new(MAX_DEPTH, MAX_DEPTH + 1))
+        );
+
+        let s = "-".repeat(MAX_DEPTH) + "a";
+        assert!(expand::<i64, 4>(&s).is_ok());
+    }
+}
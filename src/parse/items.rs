@@ -1,111 +1,147 @@
-use std::str::Chars;
+use super::{Error, Span};
+use crate::{OverflowError, ParseError};
+use core::{convert::TryFrom, fmt, iter::Peekable, str::CharIndices};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Operator {
     Add,
+    Sub,
+    Mul,
     Pow,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Item {
     Integer(i64),
+    /// An integer too large for an `i64`, whose digits are the text of its token
+    Large,
+    /// A coefficient written as `numerator/denominator` or with a decimal point
+    Ratio(i64, i64),
     Operator(Operator),
     Variable(u8),
+    Open,
+    Close,
 }
 
-enum State {
-    Init,
-    Integer { start: usize },
-    Item(Item),
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Item::Integer(int) => write!(f, "`{}`", int),
+            Item::Large => f.write_str("large integer"),
+            Item::Ratio(num, den) => write!(f, "`{}/{}`", num, den),
+            Item::Operator(Operator::Add) => f.write_str("`+`"),
+            Item::Operator(Operator::Sub) => f.write_str("`-`"),
+            Item::Operator(Operator::Mul) => f.write_str("`*`"),
+            Item::Operator(Operator::Pow) => f.write_str("`^`"),
+            Item::Variable(var) => write!(f, "`{}`", char::from(b'a' + var)),
+            Item::Open => f.write_str("`(`"),
+            Item::Close => f.write_str("`)`"),
+        }
+    }
+}
+
+/// An item along with its location in the source string
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub item: Item,
+    pub span: Span,
 }
 
 pub struct Items<'a> {
     s: &'a str,
-    chars: Chars<'a>,
-    pos: usize,
-    state: State,
+    chars: Peekable<CharIndices<'a>>,
 }
 
 impl<'a> Items<'a> {
     pub fn new(s: &'a str) -> Self {
         Self {
             s,
-            chars: s.chars(),
-            pos: 0,
-            state: State::Init,
+            chars: s.char_indices().peekable(),
+        }
+    }
+
+    /// The text of the input at `span`
+    pub fn slice(&self, span: Span) -> &'a str {
+        &self.s[span.start..span.end]
+    }
+
+    /// Skip past any digits, returning the end of the last one or `end` if there are none
+    fn digits(&mut self, mut end: usize) -> usize {
+        while let Some(&(pos, '0'..='9')) = self.chars.peek() {
+            end = pos + 1;
+            self.chars.next();
+        }
+        end
+    }
+
+    fn integer(&mut self, start: usize) -> Result<Token, Error> {
+        let end = self.digits(start + 1);
+        let overflow = |span| Error::new(ParseError::Overflow(OverflowError::Coefficient), span);
+        let int = self.s[start..end].parse::<i64>();
+
+        let (pos, sep) = match self.chars.peek() {
+            Some(&(pos, sep @ '/')) | Some(&(pos, sep @ '.')) => (pos, sep),
+            _ => {
+                return Ok(Token {
+                    item: int.map_or(Item::Large, Item::Integer),
+                    span: Span::new(start, end),
+                })
+            }
+        };
+        self.chars.next();
+        let int = int.map_err(|_| overflow(Span::new(start, end)))?;
+
+        // The separator must be followed directly by more digits
+        let end = self.digits(pos + 1);
+        if end == pos + 1 {
+            return Err(Error::new(ParseError::Symbol, Span::new(pos, end)));
         }
+
+        let span = Span::new(start, end);
+        let digits: i64 = self.s[pos + 1..end].parse().map_err(|_| overflow(span))?;
+        let item = if sep == '/' {
+            Item::Ratio(int, digits)
+        } else {
+            let den = u32::try_from(end - pos - 1)
+                .ok()
+                .and_then(|places| 10i64.checked_pow(places))
+                .ok_or(overflow(span))?;
+            let num = int
+                .checked_mul(den)
+                .and_then(|int| int.checked_add(digits))
+                .ok_or(overflow(span))?;
+            Item::Ratio(num, den)
+        };
+        Ok(Token { item, span })
     }
 }
 
 impl Iterator for Items<'_> {
-    type Item = Item;
+    type Item = Result<Token, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            if let State::Item(item) = self.state {
-                self.state = State::Init;
-                break Some(item);
-            } else {
-                if let Some(c) = self.chars.next() {
-                    match c {
-                        '0'..='9' => match self.state {
-                            State::Integer { .. } => {}
-                            _ => self.state = State::Integer { start: self.pos },
-                        },
-                        ' ' => match self.state {
-                            State::Integer { start } => {
-                                self.state = State::Init;
-
-                                let a = &self.s[start..self.pos];
-                                let n = a.parse::<i64>().unwrap();
-                                self.pos += 1;
-                                break Some(Item::Integer(n));
-                            }
-                            _ => {}
-                        },
-                        c => {
-                            let item = match c {
-                                '+' => Item::Operator(Operator::Add),
-                                '^' => Item::Operator(Operator::Pow),
-                                c => {
-                                    let n = c as u8 - 97;
-                                    if n <= 4 {
-                                        Item::Variable(n)
-                                    } else {
-                                        todo!()
-                                    }
-                                }
-                            };
-                            match self.state {
-                                State::Integer { start } => {
-                                    self.state = State::Item(item);
-
-                                    let a = &self.s[start..self.pos];
-                                    let n = a.parse::<i64>().unwrap();
-                                    self.pos += 1;
-                                    break Some(Item::Integer(n));
-                                }
-                                _ => {
-                                    self.state = State::Item(item);
-                                }
-                            }
-                        }
-                    }
-                    self.pos += 1;
-                } else {
-                    match self.state {
-                        State::Integer { start } => {
-                            self.state = State::Init;
-                            let a = &self.s[start..self.pos];
-                            let n = a.parse::<i64>().unwrap();
-                            break Some(Item::Integer(n));
-                        }
-                        _ => {
-                            break None;
-                        }
-                    }
+            let (start, c) = self.chars.next()?;
+            let item = match c {
+                '0'..='9' => return Some(self.integer(start)),
+                '+' => Item::Operator(Operator::Add),
+                '-' => Item::Operator(Operator::Sub),
+                '*' => Item::Operator(Operator::Mul),
+                '^' => Item::Operator(Operator::Pow),
+                '(' => Item::Open,
+                ')' => Item::Close,
+                'a'..='z' => Item::Variable(c as u8 - b'a'),
+                c if c.is_whitespace() => continue,
+                c => {
+                    let span = Span::new(start, start + c.len_utf8());
+                    return Some(Err(Error::new(ParseError::Symbol, span)));
                 }
-            }
+            };
+
+            break Some(Ok(Token {
+                item,
+                span: Span::new(start, start + 1),
+            }));
         }
     }
 }
@@ -1,133 +1,533 @@
-use super::Monomial;
+use super::{ring::Ring, Monomial, OverflowError, ParseError};
+use core::convert::TryFrom;
+
+mod error;
+pub use error::{Diagnostic, Error, Expected, Span};
+
+#[cfg(feature = "alloc")]
+mod expr;
+#[cfg(feature = "alloc")]
+pub use expr::{expand, MAX_DEPTH, MAX_TERMS};
 
 mod items;
-pub use items::{Item, Items, Operator};
+pub use items::{Item, Items, Operator, Token};
 
+#[derive(Clone, Copy)]
 enum ParserState {
-    Init,
+    /// Beginning of the input, where it may also end
+    Start,
+    /// A term is required after an operator or sign
+    Term,
     Integer,
-    Variable { var: u8, pow: bool },
+    Variable {
+        var: u8,
+        pow: bool,
+    },
+    /// An error was returned
+    Done,
+}
+
+/// Convert the coefficient `numerator / denominator` found at `span`
+pub(crate) fn coefficient<C: Ring>(
+    numerator: i64,
+    denominator: i64,
+    span: Span,
+) -> Result<C, Error> {
+    C::from_ratio(numerator, denominator).ok_or_else(|| {
+        let kind = if denominator == 1 {
+            ParseError::Overflow(OverflowError::Coefficient)
+        } else {
+            ParseError::Coefficient
+        };
+        Error::new(kind, span)
+    })
+}
+
+/// Convert the integer too large for an `i64` with the digits `digits` found at `span`
+pub(crate) fn large<C: Ring>(digits: &str, span: Span) -> Result<C, Error> {
+    C::from_digits(digits)
+        .ok_or_else(|| Error::new(ParseError::Overflow(OverflowError::Coefficient), span))
 }
 
-pub struct Parser<'a> {
+pub struct Parser<'a, C = i64, const N: usize = 4> {
     items: Items<'a>,
-    mono: Option<Monomial>,
+    len: usize,
+    mono: Option<Monomial<C, N>>,
+    /// Where the current term appears in the input
+    term: Span,
     state: ParserState,
+    negative: bool,
 }
 
-impl<'a> Parser<'a> {
+impl<'a, C, const N: usize> Parser<'a, C, N>
+where
+    C: Ring,
+{
     pub fn new(s: &'a str) -> Self {
         Self {
             items: Items::new(s),
+            len: s.len(),
             mono: None,
-            state: ParserState::Init,
+            term: Span::new(0, 0),
+            state: ParserState::Start,
+            negative: false,
         }
     }
-}
 
-impl Iterator for Parser<'_> {
-    type Item = Monomial;
+    /// Where the most recently returned term appears in the input
+    pub fn span(&self) -> Span {
+        self.term
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Start a new monomial with the coefficient `numerator / denominator`,
+    /// applying any pending negation
+    fn start(&mut self, numerator: i64, denominator: i64, span: Span) -> Result<(), Error> {
+        let numerator = if self.negative { -numerator } else { numerator };
+        let coefficient = coefficient(numerator, denominator, span)?;
+        self.begin(coefficient, span);
+        Ok(())
+    }
+
+    /// Start a new monomial with an integer coefficient too large for an `i64`,
+    /// applying any pending negation
+    fn start_large(&mut self, span: Span) -> Result<(), Error> {
+        let mut coefficient: C = large(self.items.slice(span), span)?;
+        if self.negative {
+            coefficient = coefficient.checked_neg().ok_or(Error::new(
+                ParseError::Overflow(OverflowError::Coefficient),
+                span,
+            ))?;
+        }
+        self.begin(coefficient, span);
+        Ok(())
+    }
+
+    fn begin(&mut self, coefficient: C, span: Span) {
+        self.negative = false;
+        self.mono = Some(Monomial::coefficient(coefficient));
+        self.term = span;
+    }
+
+    /// Finish the current monomial and prepare for the next term after `op`
+    fn finish(&mut self, op: Operator) -> Option<Monomial<C, N>> {
+        self.state = ParserState::Term;
+        self.negative = matches!(op, Operator::Sub);
+        self.mono.take()
+    }
+
+    /// Raise the variable `var` of the current monomial by `exp`, reporting overflow at `span`
+    fn raise(&mut self, var: u8, exp: i64, span: Span) -> Result<(), Error> {
+        let mono = self.mono.as_mut().unwrap();
+        let degree = &mut mono.exponents[var as usize];
+        *degree = u16::try_from(exp)
+            .ok()
+            .and_then(|exp| degree.checked_add(exp))
+            .ok_or(Error::new(
+                ParseError::Overflow(OverflowError::Exponent),
+                span,
+            ))?;
+        Ok(())
+    }
+
+    fn parse_term(&mut self) -> Result<Option<Monomial<C, N>>, Error> {
         loop {
-            if let Some(item) = self.items.next() {
-                match self.state {
-                    ParserState::Init => {
-                        match item {
-                            Item::Variable(var) => {
-                                self.mono = Some(Monomial::default());
-                                self.state = ParserState::Variable { var, pow: false };
-                            }
-                            Item::Integer(int) => {
-                                self.state = ParserState::Integer;
-                                self.mono = Some(Monomial::coefficient(int));
-                            }
-                            _ => {
-                                // TODO Return error
-                                todo!()
-                            }
-                        }
-                    }
-                    ParserState::Integer => {
-                        match item {
-                            Item::Variable(var) => {
-                                self.state = ParserState::Variable { var, pow: false };
-                            }
-                            Item::Operator(Operator::Add) => {
-                                self.state = ParserState::Init;
-                                break self.mono.take();
-                            }
-                            _ => {
-                                // TODO Return error
-                                todo!()
-                            }
+            let token = match self.items.next() {
+                Some(Ok(token)) => token,
+                Some(Err(mut error)) => {
+                    // Integers are only known to be exponents by their position
+                    if let ParserState::Variable { pow: true, .. } = self.state {
+                        if let ParseError::Overflow(_) = error.kind {
+                            error.kind = ParseError::Overflow(OverflowError::Exponent);
                         }
                     }
-                    ParserState::Variable { var, pow } => match item {
-                        Item::Integer(exp) => {
-                            if pow {
-                                self.state = ParserState::Integer;
-                                self.mono.as_mut().unwrap().exponents[var as usize] += exp as u16;
-                            } else {
-                                // TODO Return error
-                                todo!()
-                            }
-                        }
-                        Item::Operator(op) => {
-                            match op {
-                                Operator::Add => {
-                                    if pow {
-                                        // TODO Return error
-                                        todo!()
-                                    } else {
-                                        self.state = ParserState::Init;
-                                        let mut mono = self.mono.take().unwrap();
-                                        mono.exponents[var as usize] = 1 as u16;
-                                        break Some(mono);
-                                    }
-                                }
-                                Operator::Pow => {
-                                    if pow {
-                                        // TODO Return error
-                                        todo!()
-                                    } else {
-                                        self.state = ParserState::Variable { var, pow: true };
-                                    }
-                                }
-                            }
-                        }
-                        Item::Variable(v2) => {
-                            if !pow {
-                                self.state = ParserState::Variable {
-                                    var: v2,
-                                    pow: false,
-                                };
-                                self.mono.as_mut().unwrap().exponents[var as usize] += 1;
-                            } else {
-                                // TODO Return error
-                                todo!()
-                            }
-                        }
-                    },
+                    return Err(error);
                 }
-            } else {
-                match self.state {
-                    ParserState::Variable { var, pow } => {
-                        if !pow {
-                            if let Some(ref mut mono) = self.mono {
-                                mono.exponents[var as usize] += 1;
-                            }
-                            self.state = ParserState::Init;
-                        } else {
-                            // TODO Return error
-                            todo!()
-                        }
-                    }
-                    _ => {}
+                None => return self.parse_end(),
+            };
+
+            let span = token.span;
+            if let Item::Variable(var) = token.item {
+                if usize::from(var) >= N {
+                    return Err(Error::new(ParseError::Variable, span));
                 }
+            }
 
-                break self.mono.take();
+            match (self.state, token.item) {
+                (ParserState::Start, item) | (ParserState::Term, item) => match item {
+                    Item::Variable(var) => {
+                        self.start(1, 1, span)?;
+                        self.state = ParserState::Variable { var, pow: false };
+                    }
+                    Item::Integer(int) => {
+                        self.start(int, 1, span)?;
+                        self.state = ParserState::Integer;
+                    }
+                    Item::Large => {
+                        self.start_large(span)?;
+                        self.state = ParserState::Integer;
+                    }
+                    Item::Ratio(num, den) => {
+                        self.start(num, den, span)?;
+                        self.state = ParserState::Integer;
+                    }
+                    Item::Operator(Operator::Sub) => {
+                        self.negative = !self.negative;
+                        self.state = ParserState::Term;
+                    }
+                    found => return Err(Error::unexpected(Expected::Term, Some(found), span)),
+                },
+                (ParserState::Integer, item) => match item {
+                    Item::Variable(var) => {
+                        self.term.end = span.end;
+                        self.state = ParserState::Variable { var, pow: false };
+                    }
+                    Item::Operator(op @ Operator::Add) | Item::Operator(op @ Operator::Sub) => {
+                        return Ok(self.finish(op));
+                    }
+                    found => return Err(Error::unexpected(Expected::Operator, Some(found), span)),
+                },
+                (ParserState::Variable { var, pow: false }, item) => match item {
+                    Item::Variable(next) => {
+                        // The term so far ends with the variable
+                        self.raise(var, 1, self.term)?;
+                        self.term.end = span.end;
+                        self.state = ParserState::Variable {
+                            var: next,
+                            pow: false,
+                        };
+                    }
+                    Item::Operator(Operator::Pow) => {
+                        self.term.end = span.end;
+                        self.state = ParserState::Variable { var, pow: true };
+                    }
+                    Item::Operator(op @ Operator::Add) | Item::Operator(op @ Operator::Sub) => {
+                        // The term so far ends with the variable
+                        self.raise(var, 1, self.term)?;
+                        return Ok(self.finish(op));
+                    }
+                    found => return Err(Error::unexpected(Expected::Operator, Some(found), span)),
+                },
+                (ParserState::Variable { var, pow: true }, item) => match item {
+                    Item::Integer(exp) => {
+                        self.raise(var, exp, span)?;
+                        self.term.end = span.end;
+                        self.state = ParserState::Integer;
+                    }
+                    Item::Large => {
+                        return Err(Error::new(
+                            ParseError::Overflow(OverflowError::Exponent),
+                            span,
+                        ))
+                    }
+                    found => return Err(Error::unexpected(Expected::Exponent, Some(found), span)),
+                },
+                (ParserState::Done, _) => return Ok(None),
             }
         }
     }
+
+    fn parse_end(&mut self) -> Result<Option<Monomial<C, N>>, Error> {
+        let span = Span::new(self.len, self.len);
+        match self.state {
+            ParserState::Term => return Err(Error::unexpected(Expected::Term, None, span)),
+            ParserState::Variable { pow: true, .. } => {
+                return Err(Error::unexpected(Expected::Exponent, None, span))
+            }
+            ParserState::Variable { var, pow: false } => self.raise(var, 1, self.term)?,
+            _ => {}
+        }
+
+        self.state = ParserState::Start;
+        Ok(self.mono.take())
+    }
+}
+
+impl<C, const N: usize> Iterator for Parser<'_, C, N>
+where
+    C: Ring,
+{
+    type Item = Result<Monomial<C, N>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let ParserState::Done = self.state {
+            return None;
+        }
+
+        match self.parse_term() {
+            Ok(mono) => mono.map(Ok),
+            Err(error) => {
+                self.state = ParserState::Done;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Parse the terms of `s` into `buf` as they appear without allocating,
+/// returning how many were written
+/// ```
+/// use embedded_algebra::{
+///     parse::{self, Span},
+///     Monomial, ParseError, Polynomial,
+/// };
+///
+/// let mut buf = [Monomial::default(); 4];
+/// let len = parse::parse_into("3a^2 - b + 1", &mut buf)?;
+/// assert_eq!(
+///     &buf[..len],
+///     &[Monomial::from("3a^2"), Monomial::from("-b"), Monomial::from(1)]
+/// );
+///
+/// let poly = Polynomial::<i64, 4, _>::new(&buf[..len]);
+/// assert_eq!(poly.to_string(), "3a^2 - 1b + 1");
+///
+/// let error = parse::parse_into("a + b^2", &mut buf[..1]).unwrap_err();
+/// assert_eq!(error.kind, ParseError::Capacity);
+/// assert_eq!(error.span, Span::new(4, 7));
+/// # Ok::<(), parse::Error>(())
+/// ```
+pub fn parse_into<C, const N: usize>(s: &str, buf: &mut [Monomial<C, N>]) -> Result<usize, Error>
+where
+    C: Ring,
+{
+    let mut parser = Parser::new(s);
+    let mut len = 0;
+    while let Some(mono) = parser.next() {
+        let mono = mono?;
+        let slot = buf
+            .get_mut(len)
+            .ok_or(Error::new(ParseError::Capacity, parser.span()))?;
+        *slot = mono;
+        len += 1;
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::Polynomial;
+
+    #[cfg(feature = "alloc")]
+    fn error(s: &str) -> Error {
+        s.parse::<Polynomial>().unwrap_err()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parses_binary_minus() {
+        let poly = Polynomial::from("3a - 2b");
+        assert_eq!(
+            poly.monomials(),
+            &[Monomial::from("3a"), Monomial::new(-2, [0, 1, 0, 0])]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parses_unary_minus() {
+        let poly = Polynomial::from("-a^2 + -2 - -b");
+        assert_eq!(
+            poly.monomials(),
+            &[
+                Monomial::new(-1, [2, 0, 0, 0]),
+                Monomial::coefficient(-2),
+                Monomial::from("b")
+            ]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_trips_display() {
+        for s in ["-3a^2b - 2b + 1", "2a - 1", "-1c^3 + 4ab"].iter() {
+            let poly = Polynomial::from(*s);
+            assert_eq!(Polynomial::from(&*poly.to_string()), poly);
+        }
+    }
+
+    #[test]
+    fn parses_negative_monomial() {
+        assert_eq!(Monomial::from("-3a^2"), Monomial::new(-3, [2, 0, 0, 0]));
+        assert_eq!(Monomial::from("-b"), Monomial::new(-1, [0, 1, 0, 0]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reports_unexpected_items() {
+        assert_eq!(
+            error("^a"),
+            Error::unexpected(
+                Expected::Term,
+                Some(Item::Operator(Operator::Pow)),
+                Span::new(0, 1)
+            )
+        );
+        assert_eq!(
+            error("a^"),
+            Error::unexpected(Expected::Exponent, None, Span::new(2, 2))
+        );
+        assert_eq!(
+            error("a + "),
+            Error::unexpected(Expected::Term, None, Span::new(4, 4))
+        );
+        assert_eq!(
+            error("2 3"),
+            Error::unexpected(Expected::Operator, Some(Item::Integer(3)), Span::new(2, 3))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parses_fractional_coefficients() {
+        let poly = "0.5a^2 - 3/4b".parse::<Polynomial<f64>>().unwrap();
+        assert_eq!(poly.to_string(), "0.5a^2 - 0.75b");
+
+        assert_eq!(
+            error("a + 1/2b"),
+            Error::new(ParseError::Coefficient, Span::new(4, 7))
+        );
+        assert_eq!(
+            error("1/b"),
+            Error::new(ParseError::Symbol, Span::new(1, 2))
+        );
+        assert_eq!(
+            error("a^1.5"),
+            Error::unexpected(
+                Expected::Exponent,
+                Some(Item::Ratio(15, 10)),
+                Span::new(2, 5)
+            )
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reports_unknown_symbols() {
+        assert_eq!(
+            error("a + é"),
+            Error::new(ParseError::Symbol, Span::new(4, 6))
+        );
+        assert_eq!(error("2#"), Error::new(ParseError::Symbol, Span::new(1, 2)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reports_variables_out_of_range() {
+        assert_eq!(
+            error("2e"),
+            Error::new(ParseError::Variable, Span::new(1, 2))
+        );
+        assert_eq!(
+            "2b".parse::<Monomial<i64, 1>>(),
+            Err(Error::new(ParseError::Variable, Span::new(1, 2)))
+        );
+
+        let poly = "p^2 - a".parse::<Polynomial<i64, 16>>().unwrap();
+        assert_eq!(poly.monomials()[0].exponents[15], 2);
+        assert_eq!(
+            "p + q".parse::<Polynomial<i64, 16>>().unwrap_err(),
+            Error::new(ParseError::Variable, Span::new(4, 5))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reports_overflow() {
+        assert_eq!(
+            error("99999999999999999999a"),
+            Error::new(
+                ParseError::Overflow(OverflowError::Coefficient),
+                Span::new(0, 20)
+            )
+        );
+        assert_eq!(
+            error("a^65536"),
+            Error::new(
+                ParseError::Overflow(OverflowError::Exponent),
+                Span::new(2, 7)
+            )
+        );
+        assert_eq!(
+            error("a^99999999999999999999"),
+            Error::new(
+                ParseError::Overflow(OverflowError::Exponent),
+                Span::new(2, 22)
+            )
+        );
+    }
+
+    #[test]
+    fn reports_overflow_while_parsing() {
+        assert_eq!(
+            Parser::<i64>::new("a^65535a").next(),
+            Some(Err(Error::new(
+                ParseError::Overflow(OverflowError::Exponent),
+                Span::new(0, 8)
+            )))
+        );
+        assert_eq!(
+            Parser::<i64>::new("1 + a^65535ab").nth(1),
+            Some(Err(Error::new(
+                ParseError::Overflow(OverflowError::Exponent),
+                Span::new(4, 12)
+            )))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reports_overflow_when_expanding() {
+        // Expanding multiplies whole terms, so the overflow covers the term
+        assert_eq!(
+            error("a^65535a"),
+            Error::new(
+                ParseError::Overflow(OverflowError::Exponent),
+                Span::new(0, 8)
+            )
+        );
+    }
+
+    #[test]
+    fn reports_trailing_terms_of_monomials() {
+        assert_eq!(
+            "3a^2 - b".parse::<Monomial>(),
+            Err(Error::unexpected(
+                Expected::End,
+                Some(Item::Operator(Operator::Sub)),
+                Span::new(5, 6)
+            ))
+        );
+        assert_eq!(
+            "-2 + b".parse::<Monomial>(),
+            Err(Error::unexpected(
+                Expected::End,
+                Some(Item::Operator(Operator::Add)),
+                Span::new(3, 4)
+            ))
+        );
+        assert_eq!(
+            "a^2 é".parse::<Monomial>(),
+            Err(Error::new(ParseError::Symbol, Span::new(4, 6)))
+        );
+    }
+
+    #[test]
+    fn stops_after_error() {
+        let mut parser = Parser::<i64>::new("a + ^ + b");
+        assert_eq!(parser.next(), Some(Ok(Monomial::from("a"))));
+        assert!(matches!(parser.next(), Some(Err(_))));
+        assert_eq!(parser.next(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn renders_diagnostic() {
+        let s = "3a + é";
+        assert_eq!(
+            error(s).diagnostic(s).to_string(),
+            "3a + é\n     ^ unknown symbol"
+        );
+    }
 }
@@ -0,0 +1,241 @@
+use super::Polynomial;
+use crate::{ring::Ring, Monomial};
+use core::{
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+    slice,
+};
+
+/// Types that can be viewed as a sum of monomials
+trait Terms<C, const N: usize> {
+    fn terms(&self) -> &[Monomial<C, N>];
+}
+
+impl<C, const N: usize, T> Terms<C, N> for Polynomial<C, N, T>
+where
+    T: AsRef<[Monomial<C, N>]>,
+{
+    fn terms(&self) -> &[Monomial<C, N>] {
+        self.monomials()
+    }
+}
+
+impl<C, const N: usize, T> Terms<C, N> for &Polynomial<C, N, T>
+where
+    T: AsRef<[Monomial<C, N>]>,
+{
+    fn terms(&self) -> &[Monomial<C, N>] {
+        self.monomials()
+    }
+}
+
+impl<C, const N: usize> Terms<C, N> for Monomial<C, N> {
+    fn terms(&self) -> &[Monomial<C, N>] {
+        slice::from_ref(self)
+    }
+}
+
+/// Add or subtract two sums of monomials into a canonical polynomial
+fn sum<C, const N: usize>(
+    lhs: &[Monomial<C, N>],
+    rhs: &[Monomial<C, N>],
+    negate: bool,
+) -> Polynomial<C, N>
+where
+    C: Ring,
+{
+    let rhs = rhs.iter().map(|monomial| {
+        if negate {
+            -monomial.clone()
+        } else {
+            monomial.clone()
+        }
+    });
+
+    lhs.iter()
+        .cloned()
+        .chain(rhs)
+        .collect::<Polynomial<C, N>>()
+        .into_canonical()
+}
+
+macro_rules! impl_additive {
+    ([$($g:ident),*] $lhs:ty, $rhs:ty $(where $($bound:tt)*)?) => {
+        impl<$($g,)* const N: usize> Add<$rhs> for $lhs $(where $($bound)*)? {
+            type Output = Polynomial<C, N>;
+
+            fn add(self, rhs: $rhs) -> Self::Output {
+                sum(self.terms(), rhs.terms(), false)
+            }
+        }
+
+        impl<$($g,)* const N: usize> Sub<$rhs> for $lhs $(where $($bound)*)? {
+            type Output = Polynomial<C, N>;
+
+            fn sub(self, rhs: $rhs) -> Self::Output {
+                sum(self.terms(), rhs.terms(), true)
+            }
+        }
+    };
+}
+
+macro_rules! impl_additive_int {
+    ([$($g:ident),*] $lhs:ty $(where $($bound:tt)*)?) => {
+        impl<$($g,)* const N: usize> Add<i64> for $lhs $(where $($bound)*)? {
+            type Output = Polynomial<i64, N>;
+
+            fn add(self, rhs: i64) -> Self::Output {
+                self + Monomial::from(rhs)
+            }
+        }
+
+        impl<$($g,)* const N: usize> Sub<i64> for $lhs $(where $($bound)*)? {
+            type Output = Polynomial<i64, N>;
+
+            fn sub(self, rhs: i64) -> Self::Output {
+                self - Monomial::from(rhs)
+            }
+        }
+
+        impl<$($g,)* const N: usize> Add<$lhs> for i64 $(where $($bound)*)? {
+            type Output = Polynomial<i64, N>;
+
+            fn add(self, rhs: $lhs) -> Self::Output {
+                Monomial::from(self) + rhs
+            }
+        }
+
+        impl<$($g,)* const N: usize> Sub<$lhs> for i64 $(where $($bound)*)? {
+            type Output = Polynomial<i64, N>;
+
+            fn sub(self, rhs: $lhs) -> Self::Output {
+                Monomial::from(self) - rhs
+            }
+        }
+    };
+}
+
+impl_additive!([C, T, U] Polynomial<C, N, T>, Polynomial<C, N, U> where C: Ring, T: AsRef<[Monomial<C, N>]>, U: AsRef<[Monomial<C, N>]>);
+impl_additive!([C, T, U] &Polynomial<C, N, T>, &Polynomial<C, N, U> where C: Ring, T: AsRef<[Monomial<C, N>]>, U: AsRef<[Monomial<C, N>]>);
+impl_additive!([C, T] Polynomial<C, N, T>, Monomial<C, N> where C: Ring, T: AsRef<[Monomial<C, N>]>);
+impl_additive!([C, T] Monomial<C, N>, Polynomial<C, N, T> where C: Ring, T: AsRef<[Monomial<C, N>]>);
+impl_additive!([C] Monomial<C, N>, Monomial<C, N> where C: Ring);
+
+impl_additive_int!([T] Polynomial<i64, N, T> where T: AsRef<[Monomial<i64, N>]>);
+impl_additive_int!([] Monomial<i64, N>);
+
+impl<C, const N: usize, U> AddAssign<Polynomial<C, N, U>> for Polynomial<C, N>
+where
+    C: Ring,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    fn add_assign(&mut self, rhs: Polynomial<C, N, U>) {
+        *self = sum(self.monomials(), rhs.monomials(), false);
+    }
+}
+
+impl<C, const N: usize, U> SubAssign<Polynomial<C, N, U>> for Polynomial<C, N>
+where
+    C: Ring,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    fn sub_assign(&mut self, rhs: Polynomial<C, N, U>) {
+        *self = sum(self.monomials(), rhs.monomials(), true);
+    }
+}
+
+impl<C, const N: usize> AddAssign<Monomial<C, N>> for Polynomial<C, N>
+where
+    C: Ring,
+{
+    fn add_assign(&mut self, rhs: Monomial<C, N>) {
+        *self = sum(self.monomials(), rhs.terms(), false);
+    }
+}
+
+impl<C, const N: usize> SubAssign<Monomial<C, N>> for Polynomial<C, N>
+where
+    C: Ring,
+{
+    fn sub_assign(&mut self, rhs: Monomial<C, N>) {
+        *self = sum(self.monomials(), rhs.terms(), true);
+    }
+}
+
+impl<const N: usize> AddAssign<i64> for Polynomial<i64, N> {
+    fn add_assign(&mut self, rhs: i64) {
+        *self += Monomial::from(rhs);
+    }
+}
+
+impl<const N: usize> SubAssign<i64> for Polynomial<i64, N> {
+    fn sub_assign(&mut self, rhs: i64) {
+        *self -= Monomial::from(rhs);
+    }
+}
+
+impl<C, const N: usize, T> Neg for Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsMut<[Monomial<C, N>]>,
+{
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for monomial in self.monomials_mut().iter_mut() {
+            *monomial = -monomial.clone();
+        }
+        self
+    }
+}
+
+impl<C, const N: usize, T> Neg for &Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    type Output = Polynomial<C, N>;
+
+    fn neg(self) -> Self::Output {
+        self.monomials()
+            .iter()
+            .map(|monomial| -monomial.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Monomial, Polynomial};
+
+    #[test]
+    fn add_merges_like_terms() {
+        let a = Polynomial::from("2a + b");
+        let b = Polynomial::from("3a + 1");
+        assert_eq!(a + b, Polynomial::from("5a + b + 1"));
+    }
+
+    #[test]
+    fn sub_drops_zero_terms() {
+        let a = Polynomial::from("2a + b");
+        let b = Polynomial::from("2a");
+        assert_eq!(a - b, Polynomial::from("b"));
+    }
+
+    #[test]
+    fn sums_are_canonical() {
+        let a = Monomial::from("b") + Monomial::from("a");
+        let b = Monomial::from("a") + Monomial::from("b");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn mixed_operands() {
+        let mut poly = Polynomial::from("a^2");
+        poly += Monomial::from("2a");
+        poly += 1;
+        assert_eq!(poly, Polynomial::from("a^2 + 2a + 1"));
+
+        poly -= 1;
+        assert_eq!(1 - poly, -Polynomial::from("a^2 + 2a") + 1);
+    }
+}
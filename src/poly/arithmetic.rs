@@ -0,0 +1,86 @@
+//! How arithmetic on coefficients and exponents handles overflow
+
+use crate::{
+    ring::{self, Ring},
+    Monomial, OverflowError,
+};
+
+/// The arithmetic used on coefficients and exponents
+pub(super) trait Arithmetic<C, const N: usize> {
+    fn add(lhs: &C, rhs: &C) -> Result<C, OverflowError>;
+
+    #[cfg(feature = "alloc")]
+    fn sub(lhs: &C, rhs: &C) -> Result<C, OverflowError>;
+
+    fn neg(term: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError>;
+
+    fn mul(lhs: Monomial<C, N>, rhs: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError>;
+}
+
+/// Plain operators, which panic or wrap like they do and never return an error
+#[cfg(feature = "alloc")]
+pub(super) struct Unchecked;
+
+#[cfg(feature = "alloc")]
+impl<C: Ring, const N: usize> Arithmetic<C, N> for Unchecked {
+    fn add(lhs: &C, rhs: &C) -> Result<C, OverflowError> {
+        Ok(lhs.clone() + rhs.clone())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sub(lhs: &C, rhs: &C) -> Result<C, OverflowError> {
+        Ok(lhs.clone() - rhs.clone())
+    }
+
+    fn neg(term: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError> {
+        Ok(-term)
+    }
+
+    fn mul(lhs: Monomial<C, N>, rhs: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError> {
+        Ok(lhs * rhs)
+    }
+}
+
+/// The checked operators of [`Ring`], returning an error on overflow
+pub(super) struct Checked;
+
+impl<C: Ring, const N: usize> Arithmetic<C, N> for Checked {
+    fn add(lhs: &C, rhs: &C) -> Result<C, OverflowError> {
+        lhs.checked_add(rhs).ok_or(OverflowError::Coefficient)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sub(lhs: &C, rhs: &C) -> Result<C, OverflowError> {
+        lhs.checked_sub(rhs).ok_or(OverflowError::Coefficient)
+    }
+
+    fn neg(term: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError> {
+        term.checked_neg()
+    }
+
+    fn mul(lhs: Monomial<C, N>, rhs: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError> {
+        lhs.checked_mul(rhs)
+    }
+}
+
+/// The operators of [`ring::Saturating`], which never return an error
+pub(super) struct Saturating;
+
+impl<C: ring::Saturating, const N: usize> Arithmetic<C, N> for Saturating {
+    fn add(lhs: &C, rhs: &C) -> Result<C, OverflowError> {
+        Ok(lhs.saturating_add(rhs))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sub(lhs: &C, rhs: &C) -> Result<C, OverflowError> {
+        Ok(lhs.saturating_sub(rhs))
+    }
+
+    fn neg(term: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError> {
+        Ok(term.saturating_neg())
+    }
+
+    fn mul(lhs: Monomial<C, N>, rhs: Monomial<C, N>) -> Result<Monomial<C, N>, OverflowError> {
+        Ok(lhs.saturating_mul(rhs))
+    }
+}
@@ -0,0 +1,561 @@
+use super::{
+    arithmetic::{Arithmetic, Checked, Saturating},
+    Polynomial,
+};
+use crate::{
+    parse::{self, Parser},
+    ring::{self, Ring},
+    CapacityError, Monomial, OverflowError, ParseError,
+};
+use core::{array, cmp::Reverse, fmt, iter, mem, str::FromStr};
+
+/// Storage for at most `CAP` terms held in place, for use without a heap.
+///
+/// Parsing, evaluation, sums, products and derivatives of an [`ArrayPolynomial`] never
+/// allocate, and either report overflow as an error or clamp to the bounds of the coefficients.
+/// Division, greatest common divisors, factorisation and substitution build
+/// intermediate polynomials of unknown size, so they still need the `alloc` feature.
+#[derive(Clone, Debug)]
+pub struct Array<C, const N: usize, const CAP: usize> {
+    terms: [Monomial<C, N>; CAP],
+    len: usize,
+}
+
+/// A polynomial with at most `CAP` terms, see [`Array`]
+pub type ArrayPolynomial<C, const N: usize, const CAP: usize> = Polynomial<C, N, Array<C, N, CAP>>;
+
+/// An operation on an [`ArrayPolynomial`] failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArrayError {
+    /// The result has more than `CAP` terms
+    Capacity,
+    Overflow(OverflowError),
+    /// A variable beyond the number a monomial can hold
+    Variable,
+}
+
+impl fmt::Display for ArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayError::Capacity => CapacityError.fmt(f),
+            ArrayError::Overflow(error) => error.fmt(f),
+            ArrayError::Variable => f.write_str("variable out of range"),
+        }
+    }
+}
+
+impl From<CapacityError> for ArrayError {
+    fn from(_: CapacityError) -> Self {
+        ArrayError::Capacity
+    }
+}
+
+impl From<OverflowError> for ArrayError {
+    fn from(error: OverflowError) -> Self {
+        ArrayError::Overflow(error)
+    }
+}
+
+impl<C, const N: usize, const CAP: usize> Array<C, N, CAP> {
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Append a term, returning an error if the storage is full
+    pub fn push(&mut self, monomial: Monomial<C, N>) -> Result<(), CapacityError> {
+        let slot = self.terms.get_mut(self.len).ok_or(CapacityError)?;
+        *slot = monomial;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl<C, const N: usize, const CAP: usize> Array<C, N, CAP>
+where
+    C: Ring,
+{
+    pub fn new() -> Self {
+        Self {
+            terms: array::from_fn(|_| Monomial::coefficient(C::zero())),
+            len: 0,
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<Monomial<C, N>> {
+        self.len = self.len.checked_sub(1)?;
+        let empty = Monomial::coefficient(C::zero());
+        Some(mem::replace(&mut self.terms[self.len], empty))
+    }
+
+    /// Add a term to a sum of combined terms, removing it if it cancels out
+    fn insert(&mut self, term: Monomial<C, N>) -> Result<(), CapacityError> {
+        if term.coefficient.is_zero() {
+            return Ok(());
+        }
+
+        let terms = &mut self.terms[..self.len];
+        match terms
+            .iter()
+            .position(|monomial| monomial.exponents == term.exponents)
+        {
+            Some(pos) => {
+                let sum = terms[pos].coefficient.clone() + term.coefficient;
+                if sum.is_zero() {
+                    terms.swap(pos, self.len - 1);
+                    self.pop();
+                } else {
+                    terms[pos].coefficient = sum;
+                }
+                Ok(())
+            }
+            None => self.push(term),
+        }
+    }
+
+    /// Combine like terms and drop zero terms in place
+    fn combine(&mut self) {
+        let len = mem::replace(&mut self.len, 0);
+        for idx in 0..len {
+            let empty = Monomial::coefficient(C::zero());
+            let term = mem::replace(&mut self.terms[idx], empty);
+
+            // Combined terms never outnumber the terms read so far
+            let _ = self.insert(term);
+        }
+    }
+
+    fn sort(&mut self) {
+        self.terms[..self.len].sort_unstable_by_key(|monomial| Reverse(monomial.exponents));
+    }
+
+    /// Combine the terms that `terms` iterates over, calling it again for every pass,
+    /// so that only like terms with a nonzero sum take up space.
+    /// Each term comes with a location `S` that `error` reports failures at.
+    fn accumulate<A, I, S, E>(
+        terms: impl Fn() -> I,
+        error: impl Fn(ArrayError, S) -> E,
+    ) -> Result<Self, E>
+    where
+        A: Arithmetic<C, N>,
+        I: Iterator<Item = Result<(Monomial<C, N>, S), E>>,
+    {
+        let mut array = Self::new();
+        for (idx, term) in terms().enumerate() {
+            let (term, location) = term?;
+            let seen = terms().take(idx).any(|earlier| {
+                earlier.is_ok_and(|(earlier, _)| earlier.exponents == term.exponents)
+            });
+            if seen {
+                continue;
+            }
+
+            let mut coefficient = C::zero();
+            for later in terms().skip(idx) {
+                let (later, location) = later?;
+                if later.exponents == term.exponents {
+                    coefficient = A::add(&coefficient, &later.coefficient)
+                        .map_err(|overflow| error(overflow.into(), location))?;
+                }
+            }
+            if !coefficient.is_zero() {
+                array
+                    .push(Monomial::new(coefficient, term.exponents))
+                    .map_err(|_| error(ArrayError::Capacity, location))?;
+            }
+        }
+        array.sort();
+        Ok(array)
+    }
+}
+
+impl<C, const N: usize, const CAP: usize> Default for Array<C, N, CAP>
+where
+    C: Ring,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, const N: usize, const CAP: usize> AsRef<[Monomial<C, N>]> for Array<C, N, CAP> {
+    fn as_ref(&self) -> &[Monomial<C, N>] {
+        &self.terms[..self.len]
+    }
+}
+
+impl<C, const N: usize, const CAP: usize> AsMut<[Monomial<C, N>]> for Array<C, N, CAP> {
+    fn as_mut(&mut self) -> &mut [Monomial<C, N>] {
+        &mut self.terms[..self.len]
+    }
+}
+
+impl<C, const N: usize, const CAP: usize> ArrayPolynomial<C, N, CAP>
+where
+    C: Ring,
+{
+    /// Combine `terms` into a polynomial in canonical order, returning an error if
+    /// more than `CAP` distinct terms are left after cancelling or a coefficient overflows
+    /// ```
+    /// use embedded_algebra::{poly::{ArrayError, ArrayPolynomial}, Monomial};
+    ///
+    /// let terms = [Monomial::from("a"), Monomial::from("2b"), Monomial::from("a")];
+    /// let poly = ArrayPolynomial::<i64, 4, 2>::try_from_terms(terms.iter().copied())?;
+    /// assert_eq!(poly.to_string(), "2a + 2b");
+    ///
+    /// let poly = ArrayPolynomial::<i64, 4, 1>::try_from_terms(terms.iter().copied());
+    /// assert_eq!(poly, Err(ArrayError::Capacity));
+    /// # Ok::<(), ArrayError>(())
+    /// ```
+    pub fn try_from_terms<I>(terms: I) -> Result<Self, ArrayError>
+    where
+        I: IntoIterator<Item = Monomial<C, N>>,
+        I::IntoIter: Clone,
+    {
+        let terms = terms.into_iter();
+        Self::try_from_results::<Checked, _>(|| terms.clone().map(Ok))
+    }
+
+    fn try_from_results<A, I>(terms: impl Fn() -> I) -> Result<Self, ArrayError>
+    where
+        A: Arithmetic<C, N>,
+        I: Iterator<Item = Result<Monomial<C, N>, OverflowError>>,
+    {
+        let terms = || terms().map(|term| Ok((term?, ())));
+        Array::accumulate::<A, _, _, _>(terms, |error, ()| error).map(Self::new)
+    }
+
+    /// Combine like terms, drop zero terms and sort in place without allocating,
+    /// see [`Polynomial::into_canonical`]
+    pub fn canonicalize(&mut self) {
+        self.monomials.combine();
+        self.monomials.sort();
+    }
+
+    fn try_sum<A, U>(&self, rhs: &Polynomial<C, N, U>, negate: bool) -> Result<Self, ArrayError>
+    where
+        A: Arithmetic<C, N>,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        Self::try_from_results::<A, _>(|| {
+            let rhs = rhs.monomials().iter().map(move |monomial| {
+                if negate {
+                    A::neg(monomial.clone())
+                } else {
+                    Ok(monomial.clone())
+                }
+            });
+            self.monomials().iter().cloned().map(Ok).chain(rhs)
+        })
+    }
+
+    fn try_neg_with<A>(&self) -> Result<Self, ArrayError>
+    where
+        A: Arithmetic<C, N>,
+    {
+        Self::try_from_results::<A, _>(|| self.monomials().iter().cloned().map(A::neg))
+    }
+
+    fn try_mul_with<A, U>(&self, rhs: &Polynomial<C, N, U>) -> Result<Self, ArrayError>
+    where
+        A: Arithmetic<C, N>,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        Self::try_from_results::<A, _>(|| {
+            self.monomials().iter().flat_map(move |lhs| {
+                rhs.monomials()
+                    .iter()
+                    .map(move |rhs| A::mul(lhs.clone(), rhs.clone()))
+            })
+        })
+    }
+
+    /// Add another polynomial, returning an error if the sum has more than `CAP` terms
+    /// or a coefficient overflows
+    pub fn try_add<U>(&self, rhs: &Polynomial<C, N, U>) -> Result<Self, ArrayError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        self.try_sum::<Checked, U>(rhs, false)
+    }
+
+    /// Subtract another polynomial, returning an error if the difference has more than `CAP`
+    /// terms or a coefficient overflows
+    pub fn try_sub<U>(&self, rhs: &Polynomial<C, N, U>) -> Result<Self, ArrayError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        self.try_sum::<Checked, U>(rhs, true)
+    }
+
+    /// Negate this, returning an error if it has more than `CAP` terms
+    /// once like terms are combined or a coefficient overflows
+    pub fn try_neg(&self) -> Result<Self, ArrayError> {
+        self.try_neg_with::<Checked>()
+    }
+
+    /// Multiply by another polynomial, returning an error if the product has more than `CAP`
+    /// terms or a coefficient or exponent overflows.
+    /// Like terms are summed before they take up space, so only the final product has to fit.
+    /// ```
+    /// use embedded_algebra::{poly::{ArrayError, ArrayPolynomial}, Monomial};
+    ///
+    /// type Poly = ArrayPolynomial<i64, 4, 3>;
+    ///
+    /// let a = Poly::try_from_terms([Monomial::from("a"), Monomial::from(1)])?;
+    /// let b = Poly::try_from_terms([Monomial::from("a^2"), Monomial::from("-a"), Monomial::from(1)])?;
+    /// assert_eq!(a.try_mul(&b)?.to_string(), "1a^3 + 1");
+    ///
+    /// let c = Poly::try_from_terms([Monomial::from("b"), Monomial::from(1)])?;
+    /// assert_eq!(a.try_mul(&c), Err(ArrayError::Capacity));
+    /// # Ok::<(), ArrayError>(())
+    /// ```
+    pub fn try_mul<U>(&self, rhs: &Polynomial<C, N, U>) -> Result<Self, ArrayError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        self.try_mul_with::<Checked, U>(rhs)
+    }
+
+    /// Add another polynomial, clamping coefficients to their bounds instead of overflowing,
+    /// or return an error if the sum has more than `CAP` terms
+    /// ```
+    /// use embedded_algebra::poly::ArrayPolynomial;
+    ///
+    /// let a: ArrayPolynomial<i8, 4, 2> = "100a + 100".parse().unwrap();
+    /// assert_eq!(a.try_saturating_add(&a).unwrap().to_string(), "127a + 127");
+    /// ```
+    pub fn try_saturating_add<U>(&self, rhs: &Polynomial<C, N, U>) -> Result<Self, CapacityError>
+    where
+        C: ring::Saturating,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        // Saturating arithmetic can only run out of capacity
+        self.try_sum::<Saturating, U>(rhs, false)
+            .map_err(|_| CapacityError)
+    }
+
+    /// Subtract another polynomial, clamping coefficients to their bounds instead of
+    /// overflowing, or return an error if the difference has more than `CAP` terms
+    pub fn try_saturating_sub<U>(&self, rhs: &Polynomial<C, N, U>) -> Result<Self, CapacityError>
+    where
+        C: ring::Saturating,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        self.try_sum::<Saturating, U>(rhs, true)
+            .map_err(|_| CapacityError)
+    }
+
+    /// Negate this, clamping coefficients to their bounds instead of overflowing, or return
+    /// an error if it has more than `CAP` terms once like terms are combined
+    pub fn try_saturating_neg(&self) -> Result<Self, CapacityError>
+    where
+        C: ring::Saturating,
+    {
+        self.try_neg_with::<Saturating>().map_err(|_| CapacityError)
+    }
+
+    /// Multiply by another polynomial, clamping coefficients and exponents to their bounds
+    /// instead of overflowing, or return an error if the product has more than `CAP` terms
+    pub fn try_saturating_mul<U>(&self, rhs: &Polynomial<C, N, U>) -> Result<Self, CapacityError>
+    where
+        C: ring::Saturating,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        self.try_mul_with::<Saturating, U>(rhs)
+            .map_err(|_| CapacityError)
+    }
+
+    /// Differentiate with respect to the variable `var`, returning an error if a coefficient
+    /// overflows or `var` is not less than `N`, see [`Polynomial::derivative`]
+    /// ```
+    /// use embedded_algebra::poly::{ArrayError, ArrayPolynomial};
+    ///
+    /// let poly: ArrayPolynomial<i64, 4, 3> = "a^3b + 2a - b".parse().unwrap();
+    /// assert_eq!(poly.try_derivative(0)?.to_string(), "3a^2b + 2");
+    /// assert_eq!(poly.try_derivative(4), Err(ArrayError::Variable));
+    /// # Ok::<(), ArrayError>(())
+    /// ```
+    pub fn try_derivative(&self, var: usize) -> Result<Self, ArrayError> {
+        if var >= N {
+            return Err(ArrayError::Variable);
+        }
+        Self::try_from_results::<Checked, _>(|| {
+            self.monomials()
+                .iter()
+                .filter(move |monomial| monomial.exponents[var] > 0)
+                .map(move |monomial| {
+                    let exp = C::from_i64(i64::from(monomial.exponents[var]))
+                        .ok_or(OverflowError::Coefficient)?;
+                    let mut monomial = monomial.clone();
+                    monomial.coefficient = monomial
+                        .coefficient
+                        .checked_mul(&exp)
+                        .ok_or(OverflowError::Coefficient)?;
+                    monomial.exponents[var] -= 1;
+                    Ok(monomial)
+                })
+        })
+    }
+}
+
+impl<C, const N: usize, const CAP: usize> FromStr for ArrayPolynomial<C, N, CAP>
+where
+    C: Ring,
+{
+    type Err = parse::Error;
+
+    /// Parse a sum of terms into canonical form without allocating,
+    /// unlike [`parse::expand`] this does not accept products or parentheses
+    /// ```
+    /// use embedded_algebra::{parse::Span, poly::ArrayPolynomial, OverflowError, ParseError};
+    ///
+    /// let poly: ArrayPolynomial<i64, 4, 2> = "a + 2b - a + 3".parse()?;
+    /// assert_eq!(poly.to_string(), "2b + 3");
+    ///
+    /// let error = "a + 2b - 3".parse::<ArrayPolynomial<i64, 4, 2>>().unwrap_err();
+    /// assert_eq!(error.kind, ParseError::Capacity);
+    /// assert_eq!(error.span, Span::new(9, 10));
+    ///
+    /// let error = "9223372036854775807 + a + 1".parse::<ArrayPolynomial<i64, 4, 2>>().unwrap_err();
+    /// assert_eq!(error.kind, ParseError::Overflow(OverflowError::Coefficient));
+    /// assert_eq!(error.span, Span::new(26, 27));
+    /// # Ok::<(), embedded_algebra::parse::Error>(())
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Parsing again for every pass costs time but no memory
+        let terms = || {
+            let mut parser = Parser::new(s);
+            iter::from_fn(move || {
+                let term = parser.next()?;
+                Some(term.map(|term| (term, parser.span())))
+            })
+        };
+        let error = |error, span| {
+            let kind = match error {
+                ArrayError::Capacity => ParseError::Capacity,
+                ArrayError::Overflow(error) => ParseError::Overflow(error),
+                ArrayError::Variable => ParseError::Variable,
+            };
+            parse::Error::new(kind, span)
+        };
+        Array::accumulate::<Checked, _, _, _>(terms, error).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Span;
+
+    fn poly<const CAP: usize>(terms: &[&str]) -> ArrayPolynomial<i64, 4, CAP> {
+        ArrayPolynomial::try_from_terms(terms.iter().map(|&s| Monomial::from(s))).unwrap()
+    }
+
+    #[test]
+    fn combines_in_place() {
+        let mut poly = Polynomial::new(Array::<i64, 4, 4>::new());
+        for s in ["b", "2a", "-b", "a"].iter() {
+            poly.monomials.push(Monomial::from(*s)).unwrap();
+        }
+        poly.canonicalize();
+
+        assert_eq!(poly, self::poly::<4>(&["3a"]));
+        assert_eq!(poly.monomials.len(), 1);
+    }
+
+    #[test]
+    fn cancels_before_running_out_of_capacity() {
+        let a = poly::<2>(&["a", "b"]);
+        let b = poly::<2>(&["a", "-b"]);
+
+        assert_eq!(a.try_mul(&b), Ok(poly(&["a^2", "-b^2"])));
+        assert_eq!(a.try_sub(&b), Ok(poly(&["2b"])));
+        assert_eq!(a.try_add(&poly::<1>(&["c"])), Err(ArrayError::Capacity));
+    }
+
+    #[test]
+    fn only_needs_room_for_the_result() {
+        // The partial products have four distinct terms before the middle ones cancel
+        let a = poly::<2>(&["a", "1"]);
+        let b = poly::<3>(&["a^2", "-a", "1"]);
+        assert_eq!(a.try_mul(&b), Ok(poly(&["a^3", "1"])));
+        assert_eq!(poly::<2>(&["1", "a"]).try_mul(&b), Ok(poly(&["a^3", "1"])));
+
+        let terms = ["a", "b", "-b"].map(Monomial::from);
+        assert_eq!(
+            ArrayPolynomial::<i64, 4, 1>::try_from_terms(terms),
+            Ok(poly(&["a"]))
+        );
+    }
+
+    #[test]
+    fn parses_terms_that_cancel() {
+        let poly: ArrayPolynomial<i64, 4, 1> = "a + b - b".parse().unwrap();
+        assert_eq!(poly, self::poly(&["a"]));
+
+        let poly: ArrayPolynomial<i64, 4, 1> = "b - 2 + 3a + 2 - b".parse().unwrap();
+        assert_eq!(poly, self::poly(&["3a"]));
+
+        let error = "a + b - c"
+            .parse::<ArrayPolynomial<i64, 4, 1>>()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            parse::Error::new(ParseError::Capacity, Span::new(4, 5))
+        );
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let max = ArrayPolynomial::<i64, 4, 2>::try_from_terms([Monomial::coefficient(i64::MAX)])
+            .unwrap();
+        let a = poly::<2>(&["a^65535", "1"]);
+
+        assert_eq!(
+            max.try_add(&poly::<1>(&["1"])),
+            Err(OverflowError::Coefficient.into())
+        );
+        assert_eq!(max.try_mul(&max), Err(OverflowError::Coefficient.into()));
+        assert_eq!(
+            a.try_mul(&poly::<1>(&["a"])),
+            Err(OverflowError::Exponent.into())
+        );
+        assert_eq!(poly::<1>(&["a^2"]).try_derivative(0), Ok(poly(&["2a"])));
+    }
+
+    #[test]
+    fn saturates_instead_of_overflowing() {
+        fn poly<const CAP: usize>(terms: &[(i8, u16)]) -> ArrayPolynomial<i8, 1, CAP> {
+            let terms = terms.iter().map(|&(c, exp)| Monomial::new(c, [exp]));
+            ArrayPolynomial::try_from_terms(terms).unwrap()
+        }
+
+        let a = poly::<2>(&[(100, 1), (-100, 0)]);
+        let min = poly::<1>(&[(i8::MIN, 0)]);
+
+        assert_eq!(
+            a.try_saturating_add(&a),
+            Ok(poly(&[(i8::MAX, 1), (i8::MIN, 0)]))
+        );
+        assert_eq!(a.try_saturating_sub(&a), Ok(poly(&[])));
+        assert_eq!(min.try_saturating_neg(), Ok(poly(&[(i8::MAX, 0)])));
+        assert_eq!(min.try_neg(), Err(OverflowError::Coefficient.into()));
+        assert_eq!(a.try_saturating_mul(&a), Err(CapacityError));
+        assert_eq!(
+            a.try_saturating_mul(&min),
+            Ok(poly(&[(i8::MIN, 1), (i8::MAX, 0)]))
+        );
+    }
+}
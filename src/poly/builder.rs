@@ -1,15 +1,23 @@
 use crate::{Monomial, Polynomial};
+use alloc::vec::Vec;
 
-#[derive(Default)]
-pub struct Builder {
-    monomials: Vec<Monomial>,
+pub struct Builder<C = i64, const N: usize = 4> {
+    monomials: Vec<Monomial<C, N>>,
 }
 
-impl Builder {
+impl<C, const N: usize> Default for Builder<C, N> {
+    fn default() -> Self {
+        Self {
+            monomials: Vec::new(),
+        }
+    }
+}
+
+impl<C, const N: usize> Builder<C, N> {
     #[inline]
     pub fn monomial<T>(mut self, monomial: T) -> Self
     where
-        T: Into<Monomial>,
+        T: Into<Monomial<C, N>>,
     {
         self.push(monomial);
         self
@@ -18,13 +26,13 @@ impl Builder {
     #[inline]
     pub fn push<T>(&mut self, monomial: T)
     where
-        T: Into<Monomial>,
+        T: Into<Monomial<C, N>>,
     {
         self.monomials.push(monomial.into());
     }
 
     #[inline]
-    pub fn build(self) -> Polynomial {
+    pub fn build(self) -> Polynomial<C, N> {
         Polynomial::new(self.monomials.into())
     }
 }
@@ -0,0 +1,256 @@
+//! Formal derivatives and integrals
+
+#[cfg(feature = "alloc")]
+use super::Polynomial;
+use crate::{
+    ring::{ExactDiv, Ring},
+    Monomial,
+};
+#[cfg(feature = "alloc")]
+use crate::{Fraction, Gcd};
+#[cfg(feature = "alloc")]
+use core::array;
+
+fn from_exponent<C: Ring>(exp: u16) -> C {
+    C::from_i64(i64::from(exp)).expect("exponent does not fit in the coefficient")
+}
+
+impl<C, const N: usize> Monomial<C, N>
+where
+    C: Ring,
+{
+    /// Differentiate `orders[var]` times with respect to each variable `var`
+    /// ```
+    /// use embedded_algebra::Monomial;
+    ///
+    /// let mono = Monomial::from("2a^3b^2");
+    ///
+    /// assert_eq!(mono.partial_derivative(&[2, 1, 0, 0]), Monomial::from("24ab"));
+    /// assert_eq!(mono.partial_derivative(&[0, 0, 1, 0]), Monomial::coefficient(0));
+    /// ```
+    /// # Panics
+    /// If an exponent does not fit in the coefficient type
+    pub fn partial_derivative(mut self, orders: &[u16; N]) -> Self {
+        for (exp, &order) in self.exponents.iter_mut().zip(orders.iter()) {
+            if order > *exp {
+                return Self::coefficient(C::zero());
+            }
+            for factor in *exp - order..*exp {
+                self.coefficient = self.coefficient * from_exponent(factor + 1);
+            }
+            *exp -= order;
+        }
+        self
+    }
+
+    /// Differentiate with respect to the variable `var`
+    pub fn derivative(self, var: usize) -> Self {
+        let mut orders = [0; N];
+        orders[var] = 1;
+        self.partial_derivative(&orders)
+    }
+
+    /// Integrate with respect to the variable `var`,
+    /// or return `None` if the coefficient is not divisible by the new exponent
+    pub fn integral(mut self, var: usize) -> Option<Self>
+    where
+        C: ExactDiv,
+    {
+        let exp = self.exponents[var].checked_add(1)?;
+        self.coefficient = self.coefficient.exact_div(&C::from_i64(i64::from(exp))?)?;
+        self.exponents[var] = exp;
+        Some(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Differentiate `orders[var]` times with respect to each variable `var`
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("a^3b^2 + 5ab - b");
+    ///
+    /// assert_eq!(poly.partial_derivative(&[2, 1, 0, 0]), Polynomial::from("12ab"));
+    /// ```
+    /// # Panics
+    /// If an exponent does not fit in the coefficient type
+    pub fn partial_derivative(&self, orders: &[u16; N]) -> Polynomial<C, N> {
+        self.monomials()
+            .iter()
+            .map(|monomial| monomial.clone().partial_derivative(orders))
+            .collect::<Polynomial<C, N>>()
+            .into_canonical()
+    }
+
+    /// Differentiate with respect to the variable `var`
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("3a^2b + 2b - 7");
+    ///
+    /// assert_eq!(poly.derivative(0), Polynomial::from("6ab"));
+    /// assert_eq!(poly.derivative(1), Polynomial::from("3a^2 + 2"));
+    /// ```
+    pub fn derivative(&self, var: usize) -> Polynomial<C, N> {
+        let mut orders = [0; N];
+        orders[var] = 1;
+        self.partial_derivative(&orders)
+    }
+
+    /// The derivatives with respect to every variable
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly: Polynomial<i64, 2> = "a^2b - 3b".parse().unwrap();
+    /// let [da, db] = poly.gradient();
+    ///
+    /// assert_eq!(da.to_string(), "2ab");
+    /// assert_eq!(db.to_string(), "1a^2 - 3");
+    /// ```
+    pub fn gradient(&self) -> [Polynomial<C, N>; N] {
+        array::from_fn(|var| self.derivative(var))
+    }
+
+    /// Integrate with respect to the variable `var` with no constant term,
+    /// or return `None` if a coefficient is not divisible by its new exponent,
+    /// see [`Polynomial::integral_fraction`] for integer coefficients
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("4a^3 + 2");
+    /// assert_eq!(poly.integral(0), Some(Polynomial::from("a^4 + 2a")));
+    /// assert_eq!(Polynomial::from("a").integral(0), None);
+    ///
+    /// let poly = "a + 1".parse::<Polynomial<f64>>().unwrap();
+    /// assert_eq!(poly.integral(0).unwrap().to_string(), "0.5a^2 + 1a");
+    /// ```
+    pub fn integral(&self, var: usize) -> Option<Polynomial<C, N>>
+    where
+        C: ExactDiv,
+    {
+        self.monomials()
+            .iter()
+            .map(|monomial| monomial.clone().integral(var))
+            .collect::<Option<Polynomial<C, N>>>()
+            .map(Polynomial::into_canonical)
+    }
+
+    /// Integrate with respect to the variable `var` with no constant term,
+    /// as a polynomial over the least common denominator of its coefficients
+    /// ```
+    /// use embedded_algebra::{Fraction, Monomial, Polynomial};
+    ///
+    /// let poly = Polynomial::from("a^2 + 1");
+    ///
+    /// assert_eq!(
+    ///     poly.integral_fraction(0),
+    ///     Fraction::new(Polynomial::from("a^3 + 3a"), Monomial::coefficient(3))
+    /// );
+    /// ```
+    /// # Panics
+    /// If an exponent overflows
+    pub fn integral_fraction(&self, var: usize) -> Fraction<Polynomial<C, N>, Monomial<C, N>>
+    where
+        C: ExactDiv + Gcd<Output = C>,
+    {
+        let divisor = |monomial: &Monomial<C, N>| {
+            let exp = monomial.exponents[var]
+                .checked_add(1)
+                .expect("exponent overflow");
+            from_exponent::<C>(exp)
+        };
+
+        let lcm = self.monomials().iter().fold(C::one(), |lcm, monomial| {
+            let d = divisor(monomial);
+            let factor = d.exact_div(&lcm.gcd(&d)).unwrap();
+            lcm * factor
+        });
+
+        let numerator = self
+            .monomials()
+            .iter()
+            .map(|monomial| {
+                let mut monomial = monomial.clone();
+                let scale = lcm.exact_div(&divisor(&monomial)).unwrap();
+                monomial.coefficient = monomial.coefficient * scale;
+                monomial.exponents[var] += 1;
+                monomial
+            })
+            .collect::<Polynomial<C, N>>()
+            .into_canonical();
+
+        Fraction::new(numerator, Monomial::coefficient(lcm)).into_simplified()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Monomial;
+    #[cfg(feature = "alloc")]
+    use crate::Polynomial;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn integrates_then_differentiates() {
+        let poly = Polynomial::from("3a^2b - 2b^3 + 5");
+        for var in 0..2 {
+            let frac = poly.integral_fraction(var);
+            let derivative = frac.numerator.derivative(var);
+
+            assert_eq!(derivative, poly.clone() * frac.denominator);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn differentiates_constants_to_zero() {
+        assert!(Polynomial::from("7").derivative(0).is_zero());
+        assert!(Polynomial::from("a^2").derivative(1).monomials().is_empty());
+    }
+
+    #[test]
+    fn differentiates_monomials() {
+        assert_eq!(
+            Monomial::<i8>::from(100).partial_derivative(&[0; 4]),
+            Monomial::from(100)
+        );
+
+        let mono = Monomial::new(3, [4, 2, 1, 0]);
+        assert_eq!(mono.derivative(0), Monomial::new(12, [3, 2, 1, 0]));
+        assert_eq!(mono.derivative(1), Monomial::new(6, [4, 1, 1, 0]));
+        assert_eq!(mono.derivative(2), Monomial::new(3, [4, 2, 0, 0]));
+        assert_eq!(mono.derivative(3), Monomial::coefficient(0));
+    }
+
+    #[test]
+    fn differentiates_monomials_repeatedly() {
+        let mono = Monomial::new(3, [4, 2, 1, 0]);
+        assert_eq!(
+            mono.partial_derivative(&[3, 0, 0, 0]),
+            Monomial::new(72, [1, 2, 1, 0])
+        );
+        assert_eq!(
+            mono.partial_derivative(&[2, 2, 1, 0]),
+            Monomial::new(72, [2, 0, 0, 0])
+        );
+        assert_eq!(
+            mono.partial_derivative(&[1, 1, 0, 0]),
+            mono.derivative(1).derivative(0)
+        );
+        assert_eq!(
+            mono.partial_derivative(&[0, 3, 0, 0]),
+            Monomial::coefficient(0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "exponent does not fit in the coefficient")]
+    fn panics_when_an_exponent_does_not_fit() {
+        Monomial::<i8>::new(1, [200, 0, 0, 0]).derivative(0);
+    }
+}
@@ -0,0 +1,440 @@
+//! Arithmetic that reports or clamps overflow instead of panicking or wrapping
+
+use super::{
+    arithmetic::{Arithmetic, Checked, Saturating},
+    div::leading,
+    Polynomial,
+};
+use crate::{
+    order::MonomialOrder,
+    ring::{self, ExactDiv, Ring},
+    Monomial, OverflowError,
+};
+use alloc::{vec, vec::Vec};
+use core::cmp::{Ordering, Reverse};
+
+/// Sort terms by descending exponents and add like terms in their original order
+pub(super) fn combine<A, C, const N: usize>(
+    mut terms: Vec<Monomial<C, N>>,
+) -> Result<Vec<Monomial<C, N>>, OverflowError>
+where
+    A: Arithmetic<C, N>,
+    C: Ring,
+{
+    terms.sort_by_key(|term| Reverse(term.exponents));
+
+    let mut combined: Vec<Monomial<C, N>> = Vec::with_capacity(terms.len());
+    for term in terms {
+        match combined.last_mut() {
+            Some(last) if last.exponents == term.exponents => {
+                last.coefficient = A::add(&last.coefficient, &term.coefficient)?;
+            }
+            _ => combined.push(term),
+        }
+    }
+    Ok(combined)
+}
+
+/// Drop zero terms from combined terms, which are already in canonical order
+pub(super) fn canonical<C, const N: usize>(mut terms: Vec<Monomial<C, N>>) -> Polynomial<C, N>
+where
+    C: Ring,
+{
+    terms.retain(|term| !term.coefficient.is_zero());
+    Polynomial::new(terms.into())
+}
+
+/// Add or subtract two sums of monomials by merging their combined terms,
+/// so like terms are subtracted directly rather than negated first
+fn sum<A, C, const N: usize>(
+    lhs: &[Monomial<C, N>],
+    rhs: &[Monomial<C, N>],
+    negate: bool,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    A: Arithmetic<C, N>,
+    C: Ring,
+{
+    let lhs = combine::<A, C, N>(lhs.to_vec())?;
+    let rhs = combine::<A, C, N>(rhs.to_vec())?;
+
+    let mut terms = Vec::with_capacity(lhs.len() + rhs.len());
+    let mut lhs = lhs.into_iter().peekable();
+    let mut rhs = rhs.into_iter().peekable();
+    loop {
+        let ordering = match (lhs.peek(), rhs.peek()) {
+            (Some(a), Some(b)) => b.exponents.cmp(&a.exponents),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        let term = match ordering {
+            Ordering::Less => lhs.next().unwrap(),
+            Ordering::Greater if negate => A::neg(rhs.next().unwrap())?,
+            Ordering::Greater => rhs.next().unwrap(),
+            Ordering::Equal => {
+                let mut term = lhs.next().unwrap();
+                let other = rhs.next().unwrap();
+                term.coefficient = if negate {
+                    A::sub(&term.coefficient, &other.coefficient)?
+                } else {
+                    A::add(&term.coefficient, &other.coefficient)?
+                };
+                term
+            }
+        };
+        terms.push(term);
+    }
+    Ok(canonical(terms))
+}
+
+fn neg<A, C, const N: usize>(terms: &[Monomial<C, N>]) -> Result<Polynomial<C, N>, OverflowError>
+where
+    A: Arithmetic<C, N>,
+    C: Ring,
+{
+    let terms = combine::<A, C, N>(terms.to_vec())?
+        .into_iter()
+        .map(A::neg)
+        .collect::<Result<_, _>>()?;
+    Ok(canonical(terms))
+}
+
+pub(super) fn product<A, C, const N: usize>(
+    lhs: &[Monomial<C, N>],
+    rhs: &[Monomial<C, N>],
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    A: Arithmetic<C, N>,
+    C: Ring,
+{
+    let mut terms = Vec::with_capacity(lhs.len() * rhs.len());
+    for a in lhs {
+        for b in rhs {
+            terms.push(A::mul(a.clone(), b.clone())?);
+        }
+    }
+    Ok(canonical(combine::<A, C, N>(terms)?))
+}
+
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Add another polynomial, returning an error instead of overflowing
+    /// ```
+    /// use embedded_algebra::{Monomial, OverflowError, Polynomial};
+    ///
+    /// let a = Polynomial::from("a + 1");
+    /// assert_eq!(a.checked_add(&Polynomial::from("2a - 1")), Ok(Polynomial::from("3a")));
+    ///
+    /// let max: Polynomial = [Monomial::coefficient(i64::MAX)].iter().copied().collect();
+    /// assert_eq!(a.checked_add(&max), Err(OverflowError::Coefficient));
+    /// ```
+    pub fn checked_add<U>(
+        &self,
+        rhs: &Polynomial<C, N, U>,
+    ) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        sum::<Checked, C, N>(self.monomials(), rhs.monomials(), false)
+    }
+
+    /// Subtract another polynomial, returning an error instead of overflowing
+    pub fn checked_sub<U>(
+        &self,
+        rhs: &Polynomial<C, N, U>,
+    ) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        sum::<Checked, C, N>(self.monomials(), rhs.monomials(), true)
+    }
+
+    /// Negate this, returning an error instead of overflowing
+    pub fn checked_neg(&self) -> Result<Polynomial<C, N>, OverflowError> {
+        neg::<Checked, C, N>(self.monomials())
+    }
+
+    /// Multiply by another polynomial, returning an error instead of overflowing
+    /// a coefficient or exponent
+    /// ```
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let a = Polynomial::from("a^40000 + 1");
+    /// let b = Polynomial::from("a - 1");
+    ///
+    /// assert_eq!(a.checked_mul(&b), Ok(Polynomial::from("a^40001 - a^40000 + a - 1")));
+    /// assert_eq!(a.checked_mul(&a), Err(OverflowError::Exponent));
+    /// ```
+    pub fn checked_mul<U>(
+        &self,
+        rhs: &Polynomial<C, N, U>,
+    ) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        product::<Checked, C, N>(self.monomials(), rhs.monomials())
+    }
+
+    /// Divide every term by `rhs`, returning an error if an exponent would underflow
+    /// or a coefficient is not divisible, instead of panicking like `/=`
+    /// ```
+    /// use embedded_algebra::{Monomial, OverflowError, Polynomial};
+    ///
+    /// let poly = Polynomial::from("4a^2b + 6ab");
+    ///
+    /// assert_eq!(poly.checked_div(&Monomial::from("2ab")), Ok(Polynomial::from("2a + 3")));
+    /// assert_eq!(poly.checked_div(&Monomial::from("a^2")), Err(OverflowError::Underflow));
+    /// assert_eq!(poly.checked_div(&Monomial::from("4a")), Err(OverflowError::Coefficient));
+    /// ```
+    pub fn checked_div(&self, rhs: &Monomial<C, N>) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        C: ExactDiv,
+    {
+        let mut terms = Vec::with_capacity(self.monomials().len());
+        for term in self.monomials() {
+            let mut exponents = term.exponents;
+            for (dest, src) in exponents.iter_mut().zip(rhs.exponents.iter()) {
+                *dest = dest.checked_sub(*src).ok_or(OverflowError::Underflow)?;
+            }
+            let coefficient = term
+                .coefficient
+                .exact_div(&rhs.coefficient)
+                .ok_or(OverflowError::Coefficient)?;
+            terms.push(Monomial::new(coefficient, exponents));
+        }
+        Ok(canonical(combine::<Checked, C, N>(terms)?))
+    }
+
+    /// Divide by another polynomial like [`Polynomial::div_rem`],
+    /// returning an error instead of overflowing
+    /// ```
+    /// use embedded_algebra::{order::Lex, OverflowError, Polynomial};
+    ///
+    /// let poly: Polynomial<i8> = "a^2 - 1".parse().unwrap();
+    /// let divisor: Polynomial<i8> = "a - 1".parse().unwrap();
+    /// let (quotient, remainder) = poly.checked_div_rem(&divisor, Lex)?;
+    /// assert_eq!((quotient.to_string(), remainder.to_string()), ("1a + 1".into(), "0".into()));
+    ///
+    /// let divisor: Polynomial<i8> = "a - 100".parse().unwrap();
+    /// assert_eq!(poly.checked_div_rem(&divisor, Lex), Err(OverflowError::Coefficient));
+    /// # Ok::<(), OverflowError>(())
+    /// ```
+    ///
+    /// # Panics
+    /// If `divisor` is zero
+    pub fn checked_div_rem<U, O>(
+        &self,
+        divisor: &Polynomial<C, N, U>,
+        order: O,
+    ) -> Result<(Polynomial<C, N>, Polynomial<C, N>), OverflowError>
+    where
+        C: ExactDiv,
+        U: AsRef<[Monomial<C, N>]>,
+        O: MonomialOrder,
+    {
+        let divisor = canonical(combine::<Checked, C, N>(divisor.monomials().to_vec())?);
+        let lead = leading(divisor.monomials(), &order)
+            .map(|idx| divisor.monomials()[idx].clone())
+            .expect("attempt to divide by a zero polynomial");
+
+        let mut dividend = canonical(combine::<Checked, C, N>(self.monomials().to_vec())?);
+        let mut quotient = Vec::new();
+        let mut remainder = Vec::new();
+
+        while let Some(idx) = leading(dividend.monomials(), &order) {
+            let term = dividend.monomials()[idx].clone();
+            let subtrahend = match term.clone().checked_div(lead.clone()) {
+                Some(factor) => {
+                    quotient.push(factor.clone());
+                    product::<Checked, C, N>(divisor.monomials(), &[factor])?
+                }
+                None => {
+                    remainder.push(term.clone());
+                    canonical(vec![term])
+                }
+            };
+            dividend = sum::<Checked, C, N>(dividend.monomials(), subtrahend.monomials(), true)?;
+        }
+
+        Ok((
+            canonical(combine::<Checked, C, N>(quotient)?),
+            canonical(combine::<Checked, C, N>(remainder)?),
+        ))
+    }
+
+    /// Raise this to the power `exp` by repeated squaring, returning an error instead of
+    /// overflowing a coefficient or exponent
+    /// ```
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let poly = Polynomial::from("a - 1");
+    /// assert_eq!(poly.checked_pow(3), Ok(Polynomial::from("a^3 - 3a^2 + 3a - 1")));
+    /// assert_eq!(poly.checked_pow(0), Ok(Polynomial::from("1")));
+    ///
+    /// assert_eq!(Polynomial::from("a^300").checked_pow(300), Err(OverflowError::Exponent));
+    /// assert_eq!(Polynomial::from("2a").checked_pow(64), Err(OverflowError::Coefficient));
+    /// ```
+    pub fn checked_pow(&self, mut exp: u16) -> Result<Polynomial<C, N>, OverflowError> {
+        let mut acc = canonical(vec![Monomial::coefficient(C::one())]);
+        let mut base = canonical(combine::<Checked, C, N>(self.monomials().to_vec())?);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = product::<Checked, C, N>(acc.monomials(), base.monomials())?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = product::<Checked, C, N>(base.monomials(), base.monomials())?;
+            }
+        }
+        Ok(acc)
+    }
+
+    /// Differentiate with respect to the variable `var` like [`Polynomial::derivative`],
+    /// returning an error instead of overflowing a coefficient
+    /// ```
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let poly: Polynomial<i8> = "a^3 + 2ab".parse().unwrap();
+    /// assert_eq!(poly.checked_derivative(0).unwrap().to_string(), "3a^2 + 2b");
+    ///
+    /// let poly: Polynomial<i8> = "a^200".parse().unwrap();
+    /// assert_eq!(poly.checked_derivative(0), Err(OverflowError::Coefficient));
+    /// ```
+    pub fn checked_derivative(&self, var: usize) -> Result<Polynomial<C, N>, OverflowError> {
+        let terms = self
+            .monomials()
+            .iter()
+            .filter(|monomial| monomial.exponents[var] > 0)
+            .map(|monomial| {
+                let exp = C::from_i64(i64::from(monomial.exponents[var]))
+                    .ok_or(OverflowError::Coefficient)?;
+                let mut monomial = monomial.clone();
+                monomial.coefficient = monomial
+                    .coefficient
+                    .checked_mul(&exp)
+                    .ok_or(OverflowError::Coefficient)?;
+                monomial.exponents[var] -= 1;
+                Ok(monomial)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(canonical(combine::<Checked, C, N>(terms)?))
+    }
+
+    /// Add another polynomial, clamping coefficients to their bounds instead of overflowing
+    /// ```
+    /// use embedded_algebra::{Monomial, Polynomial};
+    ///
+    /// let a: Polynomial<i8> = "100a + 100".parse().unwrap();
+    /// assert_eq!(a.saturating_add(&a).to_string(), "127a + 127");
+    /// ```
+    pub fn saturating_add<U>(&self, rhs: &Polynomial<C, N, U>) -> Polynomial<C, N>
+    where
+        C: ring::Saturating,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        // Saturating arithmetic always succeeds
+        sum::<Saturating, C, N>(self.monomials(), rhs.monomials(), false).unwrap()
+    }
+
+    /// Subtract another polynomial, clamping coefficients to their bounds instead of overflowing
+    pub fn saturating_sub<U>(&self, rhs: &Polynomial<C, N, U>) -> Polynomial<C, N>
+    where
+        C: ring::Saturating,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        sum::<Saturating, C, N>(self.monomials(), rhs.monomials(), true).unwrap()
+    }
+
+    /// Negate this, clamping coefficients to their bounds instead of overflowing
+    pub fn saturating_neg(&self) -> Polynomial<C, N>
+    where
+        C: ring::Saturating,
+    {
+        neg::<Saturating, C, N>(self.monomials()).unwrap()
+    }
+
+    /// Multiply by another polynomial, clamping coefficients and exponents
+    /// to their bounds instead of overflowing
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let a: Polynomial<i8> = "12a^65000 - 1".parse().unwrap();
+    /// assert_eq!(a.saturating_mul(&a).to_string(), "127a^65535 - 24a^65000 + 1");
+    /// ```
+    pub fn saturating_mul<U>(&self, rhs: &Polynomial<C, N, U>) -> Polynomial<C, N>
+    where
+        C: ring::Saturating,
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        product::<Saturating, C, N>(self.monomials(), rhs.monomials()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{order::GrLex, Monomial, OverflowError, Polynomial};
+
+    fn poly(s: &str) -> Polynomial<i8, 2> {
+        s.parse().unwrap()
+    }
+
+    /// `i8::MIN` times the given term, which cannot be parsed since `128` overflows
+    fn min(exponents: [u16; 2]) -> Polynomial<i8, 2> {
+        [Monomial::new(i8::MIN, exponents)]
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn agrees_with_the_operators_without_overflow() {
+        let a = Polynomial::from("3a^2 - 2ab + b - 5");
+        let b = Polynomial::from("a^2 + 2ab - 7");
+
+        assert_eq!(a.checked_add(&b), Ok(&a + &b));
+        assert_eq!(a.checked_sub(&b), Ok(&a - &b));
+        assert_eq!(a.checked_neg(), Ok(-&a));
+        assert_eq!(a.checked_mul(&b), Ok(&a * &b));
+        assert_eq!(a.saturating_mul(&b), &a * &b);
+        assert_eq!(a.saturating_sub(&a), Polynomial::zero());
+
+        let c = Polynomial::from("a - b");
+        assert_eq!(a.checked_div_rem(&c, GrLex), Ok(a.div_rem(&c, GrLex)));
+        assert_eq!(c.checked_pow(4), Ok(Polynomial::from("(a - b)^4")));
+        assert_eq!(a.checked_derivative(1), Ok(a.derivative(1)));
+        assert_eq!(a.checked_substitute(0, &c), Ok(a.substitute(0, &c)));
+    }
+
+    #[test]
+    fn subtracts_like_terms_directly() {
+        // Negating -128 overflows, but -1 - -128 fits
+        let (a, b) = (poly("-1a - 1"), min([1, 0]));
+        assert_eq!(a.checked_sub(&b), Ok(poly("127a - 1")));
+        assert_eq!(b.checked_neg(), Err(OverflowError::Coefficient));
+        assert_eq!(poly("1").checked_sub(&b), Err(OverflowError::Coefficient));
+
+        assert_eq!(poly("-100").saturating_sub(&poly("100")), min([0, 0]));
+        assert_eq!(b.saturating_neg(), poly("127a"));
+    }
+
+    #[test]
+    fn combines_unsorted_terms() {
+        let terms = [
+            Monomial::new(100, [1, 0]),
+            Monomial::new(-100, [0, 1]),
+            Monomial::new(-100, [1, 0]),
+        ];
+        let a: Polynomial<i8, 2> = terms.iter().copied().collect();
+
+        assert_eq!(a.checked_add(&poly("b")), Ok(poly("-99b")));
+        assert_eq!(a.checked_mul(&poly("2")), Err(OverflowError::Coefficient));
+        assert_eq!(
+            a.saturating_mul(&poly("2")),
+            poly("-a").saturating_add(&min([0, 1]))
+        );
+    }
+}
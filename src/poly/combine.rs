@@ -1,46 +1,51 @@
 use super::Polynomial;
-use crate::Monomial;
+use crate::{ring::Ring, Monomial};
+use core::mem;
 
-pub struct Combine<T> {
-    poly: Polynomial<T>,
+pub struct Combine<C, const N: usize, T> {
+    poly: Polynomial<C, N, T>,
 }
 
-impl<T> From<Polynomial<T>> for Combine<T>
+impl<C, const N: usize, T> From<Polynomial<C, N, T>> for Combine<C, N, T>
 where
-    T: AsMut<[Monomial]>,
+    C: Ring,
+    T: AsMut<[Monomial<C, N>]>,
 {
-    fn from(poly: Polynomial<T>) -> Self {
+    fn from(poly: Polynomial<C, N, T>) -> Self {
         Self { poly }
     }
 }
 
-impl<T> Iterator for Combine<T>
+impl<C, const N: usize, T> Iterator for Combine<C, N, T>
 where
-    T: AsMut<[Monomial]>,
+    C: Ring,
+    T: AsMut<[Monomial<C, N>]>,
 {
-    type Item = Monomial;
+    type Item = Monomial<C, N>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut iter = self
-            .poly
-            .monomials_mut()
-            .iter_mut()
-            .filter(|monomial| monomial.coefficient > 0);
+        loop {
+            let mut iter = self
+                .poly
+                .monomials_mut()
+                .iter_mut()
+                .filter(|monomial| !monomial.coefficient.is_zero());
 
-        if let Some(next) = iter.next() {
-            let mut acc = *next;
-            next.coefficient = 0;
+            let next = iter.next()?;
+            let mut acc = next.clone();
+            next.coefficient = C::zero();
 
             for monomial in iter {
                 if monomial.exponents == acc.exponents {
-                    acc.coefficient += monomial.coefficient;
-                    monomial.coefficient = 0;
+                    let coefficient = mem::replace(&mut monomial.coefficient, C::zero());
+                    acc.coefficient = acc.coefficient + coefficient;
                 }
             }
 
-            Some(acc)
-        } else {
-            None
+            // Like terms may cancel out completely
+            if !acc.coefficient.is_zero() {
+                break Some(acc);
+            }
         }
     }
 }
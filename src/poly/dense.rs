@@ -0,0 +1,234 @@
+//! Dense univariate integer polynomials, stored as `i128` coefficients
+//! from the constant term up with no trailing zeros
+
+use super::{modp, Polynomial};
+use crate::{ring::Ring, Monomial, OverflowError};
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+
+/// Odd primes used for modular reductions
+pub(crate) const PRIMES: [u64; 40] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179,
+];
+
+/// The largest modulus a Chinese remainder reconstruction grows to
+const MAX_MODULUS: i128 = 1 << 100;
+
+pub(crate) fn trim(mut a: Vec<i128>) -> Vec<i128> {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+
+/// Collect the terms of `poly`, which may only contain `var`, by their degree
+pub(crate) fn to_dense<const N: usize>(poly: &Polynomial<i64, N>, var: usize) -> Vec<i128> {
+    let mut dense = Vec::new();
+    for monomial in poly.nonzero() {
+        let exp = usize::from(monomial.exponents[var]);
+        if exp >= dense.len() {
+            dense.resize(exp + 1, 0);
+        }
+        dense[exp] += i128::from(monomial.coefficient);
+    }
+    trim(dense)
+}
+
+/// Like [`to_dense`] for any coefficients, or `None` unless they are integers fitting in an `i128`
+pub(crate) fn to_integers<C, const N: usize>(
+    poly: &Polynomial<C, N>,
+    var: usize,
+) -> Option<Vec<i128>>
+where
+    C: Ring,
+{
+    let mut dense = Vec::new();
+    for monomial in poly.nonzero() {
+        let exp = usize::from(monomial.exponents[var]);
+        if exp >= dense.len() {
+            dense.resize(exp + 1, 0);
+        }
+        dense[exp] = monomial.coefficient.to_i128()?.checked_add(dense[exp])?;
+    }
+    Some(trim(dense))
+}
+
+/// Build `n` out of `i64` parts, or return `None` if it cannot be represented
+fn from_i128<C: Ring>(n: i128) -> Option<C> {
+    const SHIFT: i128 = 1 << 62;
+
+    match i64::try_from(n) {
+        Ok(n) => C::from_i64(n),
+        Err(_) => from_i128::<C>(n / SHIFT)?
+            .checked_mul(&C::from_i64(SHIFT as i64)?)?
+            .checked_add(&C::from_i64((n % SHIFT) as i64)?),
+    }
+}
+
+/// Like [`from_dense`] for any coefficients, or `None` if one cannot be represented
+pub(crate) fn from_integers<C, const N: usize>(
+    dense: &[i128],
+    var: usize,
+) -> Option<Polynomial<C, N>>
+where
+    C: Ring,
+{
+    dense
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, &c)| c != 0)
+        .map(|(exp, &c)| Some(Monomial::new(from_i128(c)?, [0; N]).exponent(var, exp as u16)))
+        .collect()
+}
+
+/// # Panics
+/// If a coefficient does not fit in an `i64`
+pub(crate) fn from_dense<const N: usize>(dense: &[i128], var: usize) -> Polynomial<i64, N> {
+    checked_from_dense(dense, var).expect("coefficient overflows `i64`")
+}
+
+pub(crate) fn checked_from_dense<const N: usize>(
+    dense: &[i128],
+    var: usize,
+) -> Result<Polynomial<i64, N>, OverflowError> {
+    dense
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, &c)| c != 0)
+        .map(|(exp, &c)| {
+            let c = i64::try_from(c).map_err(|_| OverflowError::Coefficient)?;
+            Ok(Monomial::coefficient(c).exponent(var, exp as u16))
+        })
+        .collect()
+}
+
+/// The representative of `c` modulo `m` closest to zero
+pub(crate) fn symmetric(c: i128, m: i128) -> i128 {
+    let c = c.rem_euclid(m);
+    if c > m / 2 {
+        c - m
+    } else {
+        c
+    }
+}
+
+pub(crate) fn mul_mod(a: &[i128], b: &[i128], m: i128) -> Vec<i128> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut out = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] = (out[i + j] + x * y % m).rem_euclid(m);
+        }
+    }
+    trim(out)
+}
+
+pub(crate) fn gcd_int(mut m: i128, mut n: i128) -> i128 {
+    while m != 0 {
+        let old_m = m;
+        m = n % m;
+        n = old_m;
+    }
+    n.abs()
+}
+
+/// Divide out the content and make the leading coefficient positive
+pub(crate) fn primitive(a: Vec<i128>) -> Vec<i128> {
+    let a = trim(a);
+    let mut content = a.iter().fold(0, |acc, &c| gcd_int(acc, c));
+    if a.last().is_some_and(|&lead| lead < 0) {
+        content = -content;
+    }
+    if content == 0 {
+        a
+    } else {
+        a.into_iter().map(|c| c / content).collect()
+    }
+}
+
+/// Exact division over the integers, or `None` if `b` does not divide `a`
+pub(crate) fn div_exact(a: &[i128], b: &[i128]) -> Option<Vec<i128>> {
+    let db = b.len().checked_sub(1)?;
+    if a.len() < b.len() {
+        return if a.is_empty() { Some(Vec::new()) } else { None };
+    }
+
+    let mut rem = a.to_vec();
+    let mut quot = vec![0; a.len() - db];
+    for i in (0..quot.len()).rev() {
+        let c = rem[i + db];
+        if c % b[db] != 0 {
+            return None;
+        }
+        let c = c / b[db];
+        quot[i] = c;
+        for (j, &y) in b.iter().enumerate() {
+            rem[i + j] = rem[i + j].checked_sub(c.checked_mul(y)?)?;
+        }
+    }
+
+    if rem.iter().all(|&c| c == 0) {
+        Some(quot)
+    } else {
+        None
+    }
+}
+
+/// Greatest common divisor of two nonzero primitive polynomials by computing it
+/// modulo several primes and combining the images by the Chinese remainder theorem,
+/// or `None` if the primes run out before the result is found
+pub(crate) fn gcd_modular(a: &[i128], b: &[i128]) -> Option<Vec<i128>> {
+    let (lead_a, lead_b) = (*a.last()?, *b.last()?);
+    let scale = gcd_int(lead_a, lead_b);
+
+    // The images of `scale * gcd` for primes giving the smallest degree seen so far
+    let mut image: Vec<i128> = Vec::new();
+    let mut degree = usize::MAX;
+    let mut modulus = 1;
+
+    for &p in PRIMES
+        .iter()
+        .filter(|&&p| lead_a % i128::from(p) != 0 && lead_b % i128::from(p) != 0)
+    {
+        let g = modp::gcd(&modp::from_ints(a, p), &modp::from_ints(b, p), p);
+        let g = modp::scale(&g, scale.rem_euclid(i128::from(p)) as u64, p);
+        let d = g.len() - 1;
+        if d == 0 {
+            return Some(vec![1]);
+        }
+        if d > degree {
+            // Unlucky prime
+            continue;
+        }
+        if d < degree {
+            // Every earlier prime was unlucky
+            degree = d;
+            image = vec![0; d + 1];
+            modulus = 1;
+        }
+
+        // Combine x = image (mod modulus) with x = g (mod p)
+        let p = i128::from(p);
+        let inv = i128::from(modp::inv((modulus % p) as u64, p as u64));
+        for (c, &r) in image.iter_mut().zip(&g) {
+            let t = ((i128::from(r) - *c).rem_euclid(p) * inv) % p;
+            *c += modulus * t;
+        }
+        modulus *= p;
+
+        let candidate = primitive(image.iter().map(|&c| symmetric(c, modulus)).collect());
+        if div_exact(a, &candidate).is_some() && div_exact(b, &candidate).is_some() {
+            return Some(candidate);
+        }
+        if modulus > MAX_MODULUS {
+            return None;
+        }
+    }
+    None
+}
@@ -0,0 +1,143 @@
+use super::Polynomial;
+use crate::{
+    order::{Lex, MonomialOrder},
+    ring::ExactDiv,
+    Monomial,
+};
+use alloc::vec::Vec;
+use core::ops::DivAssign;
+
+/// Find the index of the greatest term under `order`
+pub(crate) fn leading<C, O, const N: usize>(terms: &[Monomial<C, N>], order: &O) -> Option<usize>
+where
+    O: MonomialOrder,
+{
+    terms
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| order.compare(&a.exponents, &b.exponents))
+        .map(|(idx, _)| idx)
+}
+
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: ExactDiv,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Divide by another polynomial, returning the quotient and remainder.
+    ///
+    /// Leading terms are taken under `order`, and the result satisfies
+    /// `self == quotient * divisor + remainder` where no term of the remainder
+    /// is divisible by the leading term of `divisor`.
+    /// ```
+    /// use embedded_algebra::{order::Lex, Polynomial};
+    ///
+    /// let poly = Polynomial::from("a^2 - 1");
+    /// let (quotient, remainder) = poly.div_rem(&Polynomial::from("a - 1"), Lex);
+    ///
+    /// assert_eq!(quotient, Polynomial::from("a + 1"));
+    /// assert_eq!(remainder, Polynomial::from("0"));
+    ///
+    /// let poly = Polynomial::from("a^2b + ab^2 + b^2");
+    /// let (quotient, remainder) = poly.div_rem(&Polynomial::from("ab - 1"), Lex);
+    ///
+    /// assert_eq!(quotient, Polynomial::from("a + b"));
+    /// assert_eq!(remainder, Polynomial::from("a + b^2 + b"));
+    /// ```
+    ///
+    /// # Panics
+    /// If `divisor` is zero
+    pub fn div_rem<U, O>(
+        &self,
+        divisor: &Polynomial<C, N, U>,
+        order: O,
+    ) -> (Polynomial<C, N>, Polynomial<C, N>)
+    where
+        U: AsRef<[Monomial<C, N>]>,
+        O: MonomialOrder,
+    {
+        let divisor = divisor
+            .monomials()
+            .iter()
+            .cloned()
+            .collect::<Polynomial<C, N>>()
+            .into_combined();
+        let lead = leading(divisor.monomials(), &order)
+            .map(|idx| divisor.monomials()[idx].clone())
+            .expect("attempt to divide by a zero polynomial");
+
+        let mut dividend = self
+            .monomials()
+            .iter()
+            .cloned()
+            .collect::<Polynomial<C, N>>()
+            .into_combined();
+        let mut quotient = Vec::new();
+        let mut remainder = Vec::new();
+
+        while let Some(idx) = leading(dividend.monomials(), &order) {
+            let term = dividend.monomials()[idx].clone();
+            if let Some(factor) = term.clone().checked_div(lead.clone()) {
+                dividend -= divisor.clone() * factor.clone();
+                quotient.push(factor);
+            } else {
+                remainder.push(term.clone());
+                dividend -= term;
+            }
+        }
+
+        (
+            quotient
+                .into_iter()
+                .collect::<Polynomial<C, N>>()
+                .into_canonical(),
+            remainder
+                .into_iter()
+                .collect::<Polynomial<C, N>>()
+                .into_canonical(),
+        )
+    }
+}
+
+impl<C, const N: usize, U> DivAssign<Polynomial<C, N, U>> for Polynomial<C, N>
+where
+    C: ExactDiv,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    /// # Panics
+    /// If `rhs` does not divide this polynomial exactly, see [`Polynomial::div_rem`]
+    fn div_assign(&mut self, rhs: Polynomial<C, N, U>) {
+        let (quotient, remainder) = self.div_rem(&rhs, Lex);
+        assert!(
+            remainder.is_zero(),
+            "polynomial is not divisible by the divisor"
+        );
+        *self = quotient;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        order::{GrLex, Lex},
+        Polynomial,
+    };
+
+    #[test]
+    fn keeps_indivisible_terms_in_remainder() {
+        let poly = Polynomial::from("a^2 + 1");
+        let (quotient, remainder) = poly.div_rem(&Polynomial::from("2a"), Lex);
+
+        assert_eq!(quotient, Polynomial::from("0"));
+        assert_eq!(remainder, Polynomial::from("a^2 + 1"));
+    }
+
+    #[test]
+    fn reconstructs_dividend() {
+        let poly = Polynomial::from("(a + b)^3 - 2ab + c");
+        let divisor = Polynomial::from("ab + c");
+        let (quotient, remainder) = poly.div_rem(&divisor, GrLex);
+
+        assert_eq!(quotient * divisor + remainder, poly.into_canonical());
+    }
+}
@@ -0,0 +1,292 @@
+//! Evaluation at a point, by nested Horner's rule for polynomials in canonical order
+
+use super::Polynomial;
+use crate::{ring::Ring, Monomial, OverflowError};
+
+/// The arithmetic used while evaluating
+trait Arithmetic<C> {
+    fn add(lhs: C, rhs: &C) -> Option<C>;
+
+    fn mul(lhs: C, rhs: &C) -> Option<C>;
+}
+
+/// Plain operators, which never return `None`
+struct Unchecked;
+
+impl<C: Ring> Arithmetic<C> for Unchecked {
+    fn add(lhs: C, rhs: &C) -> Option<C> {
+        Some(lhs + rhs.clone())
+    }
+
+    fn mul(lhs: C, rhs: &C) -> Option<C> {
+        Some(lhs * rhs.clone())
+    }
+}
+
+/// The checked operators of [`Ring`], returning `None` on overflow
+struct Checked;
+
+impl<C: Ring> Arithmetic<C> for Checked {
+    fn add(lhs: C, rhs: &C) -> Option<C> {
+        lhs.checked_add(rhs)
+    }
+
+    fn mul(lhs: C, rhs: &C) -> Option<C> {
+        lhs.checked_mul(rhs)
+    }
+}
+
+/// Raise `base` to `exp` by repeated squaring
+fn pow<A: Arithmetic<C>, C: Ring>(base: &C, mut exp: u16) -> Option<C> {
+    let mut acc = C::one();
+    let mut base = base.clone();
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = A::mul(acc, &base)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = A::mul(base.clone(), &base)?;
+        }
+    }
+    Some(acc)
+}
+
+fn eval_term<A, C, const N: usize>(term: &Monomial<C, N>, point: &[C; N]) -> Option<C>
+where
+    A: Arithmetic<C>,
+    C: Ring,
+{
+    term.exponents
+        .iter()
+        .zip(point.iter())
+        .filter(|(&exp, _)| exp > 0)
+        .try_fold(term.coefficient.clone(), |acc, (&exp, x)| {
+            A::mul(acc, &pow::<A, C>(x, exp)?)
+        })
+}
+
+/// Evaluate terms sorted by descending exponents, which agree on every variable before `var`,
+/// as a polynomial in `var` whose coefficients are evaluated the same way in the next variable
+fn horner<A, C, const N: usize>(terms: &[Monomial<C, N>], var: usize, point: &[C; N]) -> Option<C>
+where
+    A: Arithmetic<C>,
+    C: Ring,
+{
+    if var == N {
+        return terms
+            .iter()
+            .try_fold(C::zero(), |acc, term| A::add(acc, &term.coefficient));
+    }
+
+    // The value so far, still to be multiplied by `x` raised to the last degree seen
+    let x = &point[var];
+    let mut acc: Option<(C, u16)> = None;
+    let mut rest = terms;
+    while let Some(first) = rest.first() {
+        let degree = first.exponents[var];
+        let split = rest
+            .iter()
+            .position(|term| term.exponents[var] != degree)
+            .unwrap_or(rest.len());
+        let (group, tail) = rest.split_at(split);
+
+        let coefficient = horner::<A, C, N>(group, var + 1, point)?;
+        let value = match acc {
+            Some((value, last)) => A::add(
+                A::mul(value, &pow::<A, C>(x, last - degree)?)?,
+                &coefficient,
+            )?,
+            None => coefficient,
+        };
+        acc = Some((value, degree));
+        rest = tail;
+    }
+
+    match acc {
+        Some((value, last)) => A::mul(value, &pow::<A, C>(x, last)?),
+        None => Some(C::zero()),
+    }
+}
+
+fn eval<A, C, const N: usize>(terms: &[Monomial<C, N>], point: &[C; N]) -> Option<C>
+where
+    A: Arithmetic<C>,
+    C: Ring,
+{
+    if terms
+        .windows(2)
+        .all(|pair| pair[0].exponents >= pair[1].exponents)
+    {
+        horner::<A, C, N>(terms, 0, point)
+    } else {
+        terms.iter().try_fold(C::zero(), |acc, term| {
+            A::add(acc, &eval_term::<A, C, N>(term, point)?)
+        })
+    }
+}
+
+impl<C, const N: usize> Monomial<C, N>
+where
+    C: Ring,
+{
+    /// Evaluate this with each variable replaced by the corresponding value in `point`
+    /// ```
+    /// use embedded_algebra::Monomial;
+    ///
+    /// assert_eq!(Monomial::from("3a^2b").eval(&[2, 5, 7, 0]), 60);
+    /// ```
+    pub fn eval(&self, point: &[C; N]) -> C {
+        // Unchecked arithmetic always succeeds
+        eval_term::<Unchecked, C, N>(self, point).unwrap()
+    }
+
+    /// Evaluate this at `point`, returning an error instead of overflowing
+    pub fn checked_eval(&self, point: &[C; N]) -> Result<C, OverflowError> {
+        eval_term::<Checked, C, N>(self, point).ok_or(OverflowError::Coefficient)
+    }
+}
+
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Evaluate this with each variable replaced by the corresponding value in `point`.
+    ///
+    /// Polynomials in canonical order, such as parsed or computed ones, are evaluated by
+    /// nested Horner's rule, others term by term
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("3a^2 - 2a + 1");
+    /// assert_eq!(poly.eval(&[2, 0, 0, 0]), 9);
+    ///
+    /// let curve = "2a^3 - 3a + 1".parse::<Polynomial<f64>>().unwrap();
+    /// assert_eq!(curve.eval(&[0.5, 0., 0., 0.]), -0.25);
+    /// # }
+    /// ```
+    pub fn eval(&self, point: &[C; N]) -> C {
+        // Unchecked arithmetic always succeeds
+        eval::<Unchecked, C, N>(self.monomials(), point).unwrap()
+    }
+
+    /// Evaluate this at `point`, returning an error instead of overflowing
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let poly = Polynomial::from("a^3 - 1");
+    ///
+    /// assert_eq!(poly.checked_eval(&[1 << 20, 0, 0, 0]), Ok((1 << 60) - 1));
+    /// assert_eq!(
+    ///     poly.checked_eval(&[1 << 21, 0, 0, 0]),
+    ///     Err(OverflowError::Coefficient)
+    /// );
+    /// # }
+    /// ```
+    pub fn checked_eval(&self, point: &[C; N]) -> Result<C, OverflowError> {
+        eval::<Checked, C, N>(self.monomials(), point).ok_or(OverflowError::Coefficient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_into;
+    use core::{
+        convert::TryFrom,
+        ops::{Add, Mul, Neg, Sub},
+    };
+
+    /// A Q16.16 fixed-point number
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Fixed(i32);
+
+    impl Fixed {
+        fn from_f32(x: f32) -> Self {
+            Fixed((x * 65536.) as i32)
+        }
+    }
+
+    impl Add for Fixed {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self {
+            Fixed(self.0 + rhs.0)
+        }
+    }
+
+    impl Sub for Fixed {
+        type Output = Self;
+
+        fn sub(self, rhs: Self) -> Self {
+            Fixed(self.0 - rhs.0)
+        }
+    }
+
+    impl Mul for Fixed {
+        type Output = Self;
+
+        fn mul(self, rhs: Self) -> Self {
+            Fixed(((i64::from(self.0) * i64::from(rhs.0)) >> 16) as i32)
+        }
+    }
+
+    impl Neg for Fixed {
+        type Output = Self;
+
+        fn neg(self) -> Self {
+            Fixed(-self.0)
+        }
+    }
+
+    impl Ring for Fixed {
+        fn zero() -> Self {
+            Fixed(0)
+        }
+
+        fn one() -> Self {
+            Fixed(1 << 16)
+        }
+
+        fn from_i64(n: i64) -> Option<Self> {
+            n.checked_mul(1 << 16)
+                .and_then(|n| i32::try_from(n).ok())
+                .map(Fixed)
+        }
+    }
+
+    #[test]
+    fn evaluates_fixed_point() {
+        let mut buf = [Monomial::coefficient(Fixed(0)); 3];
+        let len = parse_into("4a^2 - 2a + 3", &mut buf).unwrap();
+        let curve = Polynomial::new(&buf[..len]);
+        assert_eq!(
+            curve.eval(&[Fixed::from_f32(1.5), Fixed(0), Fixed(0), Fixed(0)]),
+            Fixed::from_f32(9.)
+        );
+    }
+
+    #[test]
+    fn evaluates_any_order() {
+        let point = [2, 3, 5, 7];
+        let mut buf = [Monomial::default(); 6];
+        let len = parse_into("a^2bc + 4a^2 - ab^3 + 2c^2d + d - 6", &mut buf).unwrap();
+        let sorted = Polynomial::new(&buf[..len]);
+        let mut reversed = buf;
+        reversed.reverse();
+        let unsorted = Polynomial::new(reversed);
+
+        let expected = sorted
+            .monomials()
+            .iter()
+            .map(|term| term.eval(&point))
+            .sum::<i64>();
+        assert_eq!(expected, 60 + 16 - 54 + 350 + 7 - 6);
+        assert_eq!(sorted.eval(&point), expected);
+        assert_eq!(unsorted.eval(&point), expected);
+        assert_eq!(Polynomial::new([]).eval(&point), 0);
+    }
+}
@@ -0,0 +1,437 @@
+use super::{
+    dense::{self, from_integers, to_integers},
+    Polynomial,
+};
+#[cfg(feature = "bigint")]
+use crate::BigInt;
+use crate::{
+    order::Lex,
+    ring::{ExactDiv, Ring},
+    Gcd, Monomial, OverflowError,
+};
+use alloc::{vec, vec::Vec};
+use core::{fmt, iter};
+
+mod zassenhaus;
+
+/// A polynomial written as `sign * content * factors[0].0^factors[0].1 * ...`
+/// over the integers `C`
+#[derive(Clone, Debug)]
+pub struct Factorization<C = i64, const N: usize = 4> {
+    /// Either `1` or `-1`
+    pub sign: C,
+    /// The positive greatest common divisor of the coefficients, or zero for the zero polynomial
+    pub content: C,
+    /// Primitive factors with positive leading coefficients and their multiplicities
+    pub factors: Vec<(Polynomial<C, N>, u16)>,
+}
+
+impl<C, const N: usize> Factorization<C, N>
+where
+    C: Ring,
+{
+    /// The constant `sign * content`
+    fn constant(&self) -> C {
+        self.sign.clone() * self.content.clone()
+    }
+
+    /// Multiply the factors back together
+    pub fn expand(&self) -> Polynomial<C, N> {
+        self.factors.iter().fold(
+            iter::once(Monomial::coefficient(self.constant())).collect(),
+            |acc, (factor, multiplicity)| (0..*multiplicity).fold(acc, |acc, _| &acc * factor),
+        )
+    }
+}
+
+impl<C, const N: usize> PartialEq for Factorization<C, N>
+where
+    C: Ring,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.sign == other.sign && self.content == other.content && self.factors == other.factors
+    }
+}
+
+impl<C, const N: usize> fmt::Display for Factorization<C, N>
+where
+    C: Ring + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let constant = self.constant();
+        if self.factors.is_empty() || constant != C::one() {
+            write!(f, "{}", constant)?;
+        }
+
+        for (factor, multiplicity) in &self.factors {
+            write!(f, "({})", factor)?;
+            if *multiplicity > 1 {
+                write!(f, "^{}", multiplicity)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn variables<C, const N: usize>(poly: &Polynomial<C, N>) -> impl Iterator<Item = usize> + '_
+where
+    C: Ring,
+{
+    (0..N).filter(move |&var| poly.nonzero().any(|monomial| monomial.exponents[var] > 0))
+}
+
+fn exact_div<C, const N: usize>(
+    poly: &Polynomial<C, N>,
+    divisor: &Polynomial<C, N>,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    C: ExactDiv,
+{
+    let (quotient, remainder) = poly.checked_div_rem(divisor, Lex)?;
+    debug_assert!(remainder.is_zero());
+    Ok(quotient)
+}
+
+fn is_constant<C, const N: usize>(poly: &Polynomial<C, N>) -> bool
+where
+    C: Ring,
+{
+    variables(poly).next().is_none()
+}
+
+/// Square-free decomposition of a primitive polynomial with a positive leading coefficient
+/// by Yun's algorithm, after splitting off the part free of its first variable
+fn square_free<C, const N: usize>(
+    poly: &Polynomial<C, N>,
+) -> Result<Vec<(Polynomial<C, N>, u16)>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    let var = match variables(poly).next() {
+        Some(var) => var,
+        None => return Ok(Vec::new()),
+    };
+
+    let content = poly
+        .nonzero()
+        .map(|monomial| monomial.exponents[var])
+        .fold(Vec::<u16>::new(), |mut degrees, degree| {
+            if !degrees.contains(&degree) {
+                degrees.push(degree);
+            }
+            degrees
+        })
+        .into_iter()
+        .map(|degree| {
+            poly.nonzero()
+                .filter(|monomial| monomial.exponents[var] == degree)
+                .map(|monomial| monomial.exponent(var, 0))
+                .collect::<Polynomial<C, N>>()
+        })
+        .try_fold(Polynomial::zero(), |acc, coeff| acc.checked_gcd(&coeff))?;
+
+    let mut out = square_free(&content)?;
+    let poly = exact_div(poly, &content)?;
+
+    let d = poly.checked_derivative(var)?;
+    let a = poly.checked_gcd(&d)?;
+    let mut b = exact_div(&poly, &a)?;
+    let c = exact_div(&d, &a)?;
+    let mut d = c.checked_sub(&b.checked_derivative(var)?)?;
+
+    let mut multiplicity = 1;
+    while !is_constant(&b) {
+        let a = b.checked_gcd(&d)?;
+        b = exact_div(&b, &a)?;
+        let c = exact_div(&d, &a)?;
+        d = c.checked_sub(&b.checked_derivative(var)?)?;
+
+        if !is_constant(&a) {
+            out.push((a, multiplicity));
+        }
+        multiplicity += 1;
+    }
+    Ok(out)
+}
+
+/// Divisors of `n` found by trial division, or `None` if that would take too long
+fn divisors(n: i128) -> Option<Vec<i128>> {
+    const LIMIT: i128 = 1_000_000_000_000;
+
+    let n = n.abs();
+    if n == 0 || n > LIMIT {
+        return None;
+    }
+
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    let mut i = 1;
+    while i * i <= n {
+        if n % i == 0 {
+            small.push(i);
+            if i * i != n {
+                large.push(n / i);
+            }
+        }
+        i += 1;
+    }
+    small.extend(large.into_iter().rev());
+    Some(small)
+}
+
+/// Split off the linear factors `q x - p` of a square-free primitive polynomial
+/// for each rational root `p / q`
+fn rational_roots(mut dense: Vec<i128>) -> (Vec<Vec<i128>>, Vec<i128>) {
+    let mut linear = Vec::new();
+    let (constants, leads) = match (divisors(dense[0]), divisors(dense[dense.len() - 1])) {
+        (Some(constants), Some(leads)) => (constants, leads),
+        _ => return (linear, dense),
+    };
+
+    for &q in &leads {
+        for &p in &constants {
+            for &p in &[p, -p] {
+                // A remaining linear polynomial is its own factor
+                if dense.len() < 3 {
+                    return (linear, dense);
+                }
+
+                let factor = vec![-p, q];
+                if dense::primitive(factor.clone()) != factor {
+                    continue;
+                }
+                if let Some(quotient) = dense::div_exact(&dense, &factor) {
+                    dense = quotient;
+                    linear.push(factor);
+                }
+            }
+        }
+    }
+    (linear, dense)
+}
+
+/// Completely factor a square-free primitive univariate polynomial
+fn factor_univariate<C, const N: usize>(
+    poly: &Polynomial<C, N>,
+    var: usize,
+) -> Result<Vec<Polynomial<C, N>>, OverflowError>
+where
+    C: ExactDiv,
+{
+    let dense = to_integers(poly, var).ok_or(OverflowError::Coefficient)?;
+    let (mut factors, rest) = rational_roots(dense);
+    match rest.len() {
+        0 | 1 => {}
+        2 => factors.push(rest),
+        _ => factors.extend(zassenhaus::factor(&rest).ok_or(OverflowError::Coefficient)?),
+    }
+    factors
+        .iter()
+        .map(|factor| from_integers(factor, var).ok_or(OverflowError::Coefficient))
+        .collect()
+}
+
+fn factor<C, const N: usize, T>(
+    poly: &Polynomial<C, N, T>,
+) -> Result<Factorization<C, N>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    let poly = poly
+        .monomials()
+        .iter()
+        .cloned()
+        .collect::<Polynomial<C, N>>()
+        .into_canonical();
+
+    let sign = match poly.monomials().first() {
+        Some(lead) if lead.coefficient.is_negative() => -C::one(),
+        _ => C::one(),
+    };
+    let gcf = poly.gcf();
+    let content = if poly.is_zero() {
+        C::zero()
+    } else if gcf.coefficient.is_negative() {
+        -gcf.coefficient.clone()
+    } else {
+        gcf.coefficient.clone()
+    };
+
+    let mut factorization = Factorization {
+        sign,
+        content,
+        factors: Vec::new(),
+    };
+    if poly.is_zero() {
+        return Ok(factorization);
+    }
+
+    for var in 0..N {
+        if gcf.exponents[var] > 0 {
+            let factor = iter::once(Monomial::coefficient(C::one()).exponent(var, 1)).collect();
+            factorization.factors.push((factor, gcf.exponents[var]));
+        }
+    }
+
+    let primitive = exact_div(
+        &poly,
+        &iter::once(Monomial::new(factorization.constant(), gcf.exponents)).collect(),
+    )?;
+
+    for (part, multiplicity) in square_free(&primitive)? {
+        let vars: Vec<_> = variables(&part).collect();
+        if let [var] = vars[..] {
+            for factor in factor_univariate(&part, var)? {
+                factorization.factors.push((factor, multiplicity));
+            }
+        } else {
+            factorization.factors.push((part, multiplicity));
+        }
+    }
+    Ok(factorization)
+}
+
+impl<T, const N: usize> Polynomial<i64, N, T>
+where
+    T: AsRef<[Monomial<i64, N>]>,
+{
+    /// Factor this polynomial over the integers.
+    ///
+    /// Polynomials in a single variable are split into irreducible factors
+    /// by square-free decomposition, extraction of rational roots and
+    /// factorisation modulo a prime with Hensel lifting.
+    /// Polynomials in several variables are split into their content, monomial
+    /// and square-free parts, where square-free parts in more than one variable may still be reducible.
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("-2a^5 + 2a^4 + 2a^3 - 2a^2");
+    /// let factors = poly.factor()?;
+    ///
+    /// assert_eq!(factors.sign, -1);
+    /// assert_eq!(factors.content, 2);
+    /// assert_eq!(factors.to_string(), "-2(1a)^2(1a + 1)(1a - 1)^2");
+    /// assert_eq!(factors.expand(), poly.into_canonical());
+    /// # Ok::<(), embedded_algebra::OverflowError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// If a coefficient overflows while splitting off the square-free parts or a factor has
+    /// a coefficient that does not fit in an `i64`, or the coefficients are too large
+    /// for the factors of a univariate part to be found with certainty
+    pub fn factor(&self) -> Result<Factorization<i64, N>, OverflowError> {
+        factor(self)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<T, const N: usize> Polynomial<BigInt, N, T>
+where
+    T: AsRef<[Monomial<BigInt, N>]>,
+{
+    /// Factor this polynomial over the integers, like the `i64` version but without
+    /// overflowing while expanding the factors or splitting off the content
+    /// ```
+    /// use embedded_algebra::{BigInt, Polynomial};
+    ///
+    /// let poly: Polynomial<BigInt> = "3(a - 10000000000000)^2(a^2 + 1)".parse().unwrap();
+    /// let factors = poly.factor()?;
+    ///
+    /// assert_eq!(factors.content, BigInt::from(3));
+    /// assert_eq!(factors.to_string(), "3(1a^2 + 1)(1a - 10000000000000)^2");
+    /// # Ok::<(), embedded_algebra::OverflowError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// If the coefficients of a univariate part do not fit in an `i128`
+    /// or are too large for its factors to be found with certainty
+    pub fn factor(&self) -> Result<Factorization<BigInt, N>, OverflowError> {
+        factor(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OverflowError, Polynomial};
+
+    fn factors(s: &str) -> Vec<(Polynomial, u16)> {
+        let poly = Polynomial::from(s);
+        let factorization = poly.factor().unwrap();
+        assert_eq!(factorization.expand(), poly.into_canonical());
+
+        let mut factors = factorization.factors;
+        factors.sort_by_key(|(factor, multiplicity)| (factor.to_string(), *multiplicity));
+        factors
+    }
+
+    #[test]
+    fn factors_rational_roots() {
+        assert_eq!(
+            factors("6a^2 - a - 2"),
+            vec![
+                (Polynomial::from("2a + 1"), 1),
+                (Polynomial::from("3a - 2"), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn factors_repeated_irreducibles() {
+        assert_eq!(
+            factors("(a^2 + 1)^3(a^2 - 2)(a^4 + 1)"),
+            vec![
+                (Polynomial::from("a^2 + 1"), 3),
+                (Polynomial::from("a^2 - 2"), 1),
+                (Polynomial::from("a^4 + 1"), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn factors_high_degree() {
+        assert_eq!(
+            factors("(b^3 + b + 1)(b^4 + b^3 + b^2 + 7)(2b^5 - 3b + 2)").len(),
+            3
+        );
+    }
+
+    #[test]
+    fn factors_multivariate_square_free() {
+        assert_eq!(
+            factors("3ab^2(a + b)^2(b - 1)"),
+            vec![
+                (Polynomial::from("a"), 1),
+                (Polynomial::from("a + b"), 2),
+                (Polynomial::from("b"), 2),
+                (Polynomial::from("b - 1"), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn refuses_coefficients_beyond_lifting() {
+        assert_eq!(
+            Polynomial::from("a^64 + 1").factor(),
+            Err(OverflowError::Coefficient)
+        );
+    }
+
+    #[test]
+    fn refuses_overflowing_derivative() {
+        assert_eq!(
+            Polynomial::from("4611686018427387904a^3 + 1").factor(),
+            Err(OverflowError::Coefficient)
+        );
+    }
+
+    #[test]
+    fn factors_constants() {
+        let factorization = Polynomial::from("-12").factor().unwrap();
+        assert_eq!((factorization.sign, factorization.content), (-1, 12));
+        assert!(factorization.factors.is_empty());
+
+        let factorization = Polynomial::from("0").factor().unwrap();
+        assert_eq!(factorization.content, 0);
+    }
+}
@@ -0,0 +1,255 @@
+//! Factorisation of square-free primitive univariate integer polynomials
+//! by factoring modulo a prime, Hensel lifting and recombining the lifted factors
+
+use crate::poly::{
+    dense::{div_exact, mul_mod, primitive, symmetric, trim, PRIMES},
+    modp::{self, Poly},
+};
+use alloc::{vec, vec::Vec};
+
+/// How many suitable primes to compare before settling on the one with the fewest factors
+const CANDIDATES: usize = 5;
+
+/// The largest modulus lifted to, keeping products of residues within `i128`
+const MAX_MODULUS: i128 = 1 << 62;
+
+/// Lift `f = g h (mod p)` for monic `f`, `g`, `h` to a factorisation modulo `p^k`
+fn lift_pair(f: &[i128], g: &[u64], h: &[u64], p: u64, k: u32) -> (Vec<i128>, Vec<i128>) {
+    let (_, s, t) = modp::ext_gcd(g, h, p);
+    let to_ints = |a: &[u64]| a.iter().map(|&c| i128::from(c)).collect::<Vec<_>>();
+
+    let mut lifted_g = to_ints(g);
+    let mut lifted_h = to_ints(h);
+    let mut m = i128::from(p);
+    for _ in 1..k {
+        let next = m * i128::from(p);
+
+        // The error f - g h is divisible by m
+        let product = mul_mod(&lifted_g, &lifted_h, next);
+        let len = f.len().max(product.len());
+        let error: Vec<i128> = (0..len)
+            .map(|i| {
+                let diff = f.get(i).unwrap_or(&0) - product.get(i).unwrap_or(&0);
+                diff.rem_euclid(next) / m
+            })
+            .collect();
+        let error = modp::from_ints(&error, p);
+
+        // Solve sigma g + tau h = error with deg sigma < deg h
+        let (q, sigma) = modp::div_rem(&modp::mul(&s, &error, p), h, p);
+        let tau = modp::add(&modp::mul(&t, &error, p), &modp::mul(&q, g, p), p);
+
+        for (i, &c) in tau.iter().enumerate() {
+            if i >= lifted_g.len() {
+                lifted_g.resize(i + 1, 0);
+            }
+            lifted_g[i] += m * i128::from(c);
+        }
+        for (i, &c) in sigma.iter().enumerate() {
+            lifted_h[i] += m * i128::from(c);
+        }
+        m = next;
+    }
+    (trim(lifted_g), trim(lifted_h))
+}
+
+/// Lift a factorisation of monic `f` into monic irreducibles modulo `p` to one modulo `p^k`
+fn lift(f: &[i128], factors: &[Poly], p: u64, k: u32) -> Vec<Vec<i128>> {
+    if factors.len() == 1 {
+        return vec![f.to_vec()];
+    }
+
+    let product = |factors: &[Poly]| {
+        factors
+            .iter()
+            .fold(vec![1], |acc, factor| modp::mul(&acc, factor, p))
+    };
+    let (left, right) = factors.split_at(factors.len() / 2);
+    let (g, h) = lift_pair(f, &product(left), &product(right), p, k);
+
+    let mut out = lift(&g, left, p, k);
+    out.extend(lift(&h, right, p, k));
+    out
+}
+
+/// Choose a prime not dividing the leading coefficient for which `f` stays square-free,
+/// preferring few modular factors
+fn reduction(f: &[i128]) -> Option<(u64, Vec<Poly>)> {
+    let lead = *f.last()?;
+    PRIMES
+        .iter()
+        .copied()
+        .filter(|&p| lead % i128::from(p) != 0)
+        .filter_map(|p| {
+            let reduced = modp::monic(&modp::from_ints(f, p), p);
+            let derivative = modp::derivative(&reduced, p);
+            if modp::degree(&modp::gcd(&reduced, &derivative, p)) == Some(0) {
+                Some((p, modp::factor_square_free(&reduced, p)))
+            } else {
+                None
+            }
+        })
+        .take(CANDIDATES)
+        .min_by_key(|(_, factors)| factors.len())
+}
+
+/// Advance `indices` to the next combination of the same size drawn from `0..n`
+fn next_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    for i in (0..k).rev() {
+        if indices[i] < n - k + i {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Factor a square-free primitive polynomial with a positive leading coefficient
+/// and degree of at least two into irreducible primitive factors, or `None` if no prime
+/// keeps it square-free or the coefficients are too large to lift far enough
+/// to be sure of the result
+pub(crate) fn factor(f: &[i128]) -> Option<Vec<Vec<i128>>> {
+    let (p, modular) = reduction(f)?;
+    if modular.len() == 1 {
+        return Some(vec![f.to_vec()]);
+    }
+
+    // Any factor times the leading coefficient is bounded by lead * 2^n * |f|
+    let n = f.len() - 1;
+    let lead = f[n];
+    let norm = f
+        .iter()
+        .fold(0u128, |acc, &c| {
+            acc.saturating_add(c.unsigned_abs().saturating_pow(2))
+        })
+        .isqrt()
+        + 1;
+    let bound = norm
+        .saturating_mul(2 * lead.unsigned_abs())
+        .saturating_mul(2u128.saturating_pow(n as u32));
+
+    let mut modulus = i128::from(p);
+    let mut k = 1;
+    while (modulus as u128) <= bound && modulus * i128::from(p) <= MAX_MODULUS {
+        modulus *= i128::from(p);
+        k += 1;
+    }
+    // Below the bound the coefficients of a product of lifted factors may have wrapped around,
+    // so only factors that are irreducible modulo p can be trusted
+    let certain = (modulus as u128) > bound;
+
+    // Newton iteration for the inverse of the leading coefficient modulo p^k
+    let mut lead_inv = i128::from(modp::inv(lead.rem_euclid(i128::from(p)) as u64, p));
+    let mut m = i128::from(p);
+    while m < modulus {
+        m = (m * m).min(modulus);
+        lead_inv = (lead_inv * (2 - lead * lead_inv % m)).rem_euclid(m);
+    }
+    let monic: Vec<i128> = f
+        .iter()
+        .map(|&c| (c % modulus * lead_inv).rem_euclid(modulus))
+        .collect();
+
+    let mut lifted = lift(&monic, &modular, p, k);
+    let mut f = f.to_vec();
+    let mut out = Vec::new();
+
+    let mut size = 1;
+    while 2 * size <= lifted.len() {
+        let mut indices: Vec<usize> = (0..size).collect();
+        let found = loop {
+            let lead = *f.last().unwrap();
+            let candidate = indices
+                .iter()
+                .fold(vec![lead.rem_euclid(modulus)], |acc, &i| {
+                    mul_mod(&acc, &lifted[i], modulus)
+                });
+            let candidate = primitive(
+                candidate
+                    .into_iter()
+                    .map(|c| symmetric(c, modulus))
+                    .collect(),
+            );
+
+            if let Some(quotient) = div_exact(&f, &candidate) {
+                f = quotient;
+                out.push(candidate);
+                for &i in indices.iter().rev() {
+                    lifted.remove(i);
+                }
+                break true;
+            }
+
+            if !next_combination(&mut indices, lifted.len()) {
+                break false;
+            }
+        };
+
+        if !found {
+            if !certain {
+                return None;
+            }
+            size += 1;
+        }
+    }
+
+    out.push(primitive(f));
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(factors: &[Vec<i128>]) -> Vec<i128> {
+        factors.iter().fold(vec![1], |acc, factor| {
+            let mut out = vec![0; acc.len() + factor.len() - 1];
+            for (i, x) in acc.iter().enumerate() {
+                for (j, y) in factor.iter().enumerate() {
+                    out[i + j] += x * y;
+                }
+            }
+            out
+        })
+    }
+
+    #[test]
+    fn factors_product_of_quadratics() {
+        // (x^2 + 1)(x^2 + x + 3)
+        let f = vec![3, 1, 4, 1, 1];
+        let mut factors = factor(&f).unwrap();
+        factors.sort();
+
+        assert_eq!(factors, vec![vec![1, 0, 1], vec![3, 1, 1]]);
+    }
+
+    #[test]
+    fn keeps_irreducible() {
+        // x^4 + 1 is irreducible over the integers but splits modulo every prime
+        let f = vec![1, 0, 0, 0, 1];
+        assert_eq!(factor(&f), Some(vec![f]));
+    }
+
+    #[test]
+    fn factors_non_monic() {
+        // (2x^2 + 3)(3x^3 - x + 5)
+        let f = vec![15, -3, 10, 7, 0, 6];
+        let factors = factor(&f).unwrap();
+
+        assert_eq!(factors.len(), 2);
+        assert_eq!(expand(&factors), f);
+    }
+
+    #[test]
+    fn refuses_to_recombine_below_bound() {
+        // x^64 + 1 is irreducible, but its bound of 2^66 is out of reach
+        let mut f = vec![0; 65];
+        f[0] = 1;
+        f[64] = 1;
+        assert_eq!(factor(&f), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! Factorisation over the finite fields `GF(P)`
+
+use super::{modp, Polynomial};
+use crate::{order::Lex, ring::Ring, ModInt, Monomial};
+use alloc::vec::Vec;
+use core::{fmt, iter};
+
+/// A polynomial over `GF(P)` written as `unit * factors[0].0^factors[0].1 * ...`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModFactorization<const P: u64, const N: usize = 4> {
+    /// The leading coefficient under [`Lex`], or zero for the zero polynomial
+    pub unit: ModInt<P>,
+    /// Monic factors and their multiplicities
+    pub factors: Vec<(Polynomial<ModInt<P>, N>, u16)>,
+}
+
+impl<const P: u64, const N: usize> ModFactorization<P, N> {
+    /// Multiply the factors back together
+    pub fn expand(&self) -> Polynomial<ModInt<P>, N> {
+        self.factors.iter().fold(
+            iter::once(Monomial::coefficient(self.unit)).collect(),
+            |acc, (factor, multiplicity)| (0..*multiplicity).fold(acc, |acc, _| &acc * factor),
+        )
+    }
+}
+
+impl<const P: u64, const N: usize> fmt::Display for ModFactorization<P, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.factors.is_empty() || self.unit != ModInt::one() {
+            write!(f, "{}", self.unit)?;
+        }
+
+        for (factor, multiplicity) in &self.factors {
+            write!(f, "({})", factor)?;
+            if *multiplicity > 1 {
+                write!(f, "^{}", multiplicity)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn variables<const P: u64, const N: usize, T>(
+    poly: &Polynomial<ModInt<P>, N, T>,
+) -> impl Iterator<Item = usize> + '_
+where
+    T: AsRef<[Monomial<ModInt<P>, N>]>,
+{
+    (0..N).filter(move |&var| poly.nonzero().any(|monomial| monomial.exponents[var] > 0))
+}
+
+/// Collect the terms of `poly`, which may only contain `var`, by their degree
+fn to_dense<const P: u64, const N: usize, T>(
+    poly: &Polynomial<ModInt<P>, N, T>,
+    var: usize,
+) -> modp::Poly
+where
+    T: AsRef<[Monomial<ModInt<P>, N>]>,
+{
+    let mut dense = Vec::new();
+    for monomial in poly.nonzero() {
+        let exp = usize::from(monomial.exponents[var]);
+        if exp >= dense.len() {
+            dense.resize(exp + 1, ModInt::zero());
+        }
+        dense[exp] = dense[exp] + monomial.coefficient;
+    }
+    while dense.last() == Some(&ModInt::zero()) {
+        dense.pop();
+    }
+    dense.into_iter().map(ModInt::value).collect()
+}
+
+fn from_dense<const P: u64, const N: usize>(dense: &[u64], var: usize) -> Polynomial<ModInt<P>, N> {
+    dense
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, &c)| c != 0)
+        .map(|(exp, &c)| Monomial::coefficient(ModInt::new(c)).exponent(var, exp as u16))
+        .collect()
+}
+
+impl<const P: u64, const N: usize, T> Polynomial<ModInt<P>, N, T>
+where
+    T: AsRef<[Monomial<ModInt<P>, N>]>,
+{
+    /// Factor this polynomial over `GF(P)`, for a prime `P`.
+    ///
+    /// Polynomials in a single variable are split into monic irreducible factors
+    /// by square-free decomposition, distinct-degree and equal-degree factorisation.
+    /// Polynomials in several variables only have their monomial part split off.
+    /// ```
+    /// use embedded_algebra::{ModInt, Polynomial};
+    ///
+    /// let poly: Polynomial<ModInt<2>> = "a^5 + a^3 + a".parse().unwrap();
+    /// let factors = poly.factor();
+    ///
+    /// assert_eq!(factors.to_string(), "(1a)(1a^2 + 1a + 1)^2");
+    /// assert_eq!(factors.expand(), poly.into_canonical());
+    /// ```
+    pub fn factor(&self) -> ModFactorization<P, N> {
+        let poly = self
+            .monomials()
+            .iter()
+            .copied()
+            .collect::<Polynomial<ModInt<P>, N>>()
+            .into_canonical();
+
+        let unit = match poly.leading_coefficient(Lex) {
+            Some(unit) => unit,
+            None => {
+                return ModFactorization {
+                    unit: ModInt::zero(),
+                    factors: Vec::new(),
+                }
+            }
+        };
+
+        let gcf = poly.gcf();
+        let mut factors = Vec::new();
+        for var in 0..N {
+            if gcf.exponents[var] > 0 {
+                let factor = iter::once(Monomial::default().exponent(var, 1)).collect();
+                factors.push((factor, gcf.exponents[var]));
+            }
+        }
+
+        let divisor = Monomial::new(unit, gcf.exponents);
+        let rest = poly
+            .monomials()
+            .iter()
+            .map(|monomial| monomial.checked_div(divisor).unwrap())
+            .collect::<Polynomial<ModInt<P>, N>>();
+
+        let vars: Vec<_> = variables(&rest).collect();
+        match vars[..] {
+            [] => {}
+            [var] => {
+                for (factor, multiplicity) in modp::factor(&to_dense(&rest, var), P) {
+                    factors.push((from_dense(&factor, var), multiplicity as u16));
+                }
+            }
+            _ => factors.push((rest, 1)),
+        }
+
+        ModFactorization { unit, factors }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{order::Lex, Gcd, ModInt, Polynomial};
+
+    fn factors<const P: u64>(s: &str) -> Vec<(String, u16)> {
+        let poly: Polynomial<ModInt<P>> = s.parse().unwrap();
+        let factorization = poly.factor();
+        assert_eq!(factorization.expand(), poly);
+
+        let mut factors: Vec<_> = factorization
+            .factors
+            .iter()
+            .map(|(factor, multiplicity)| (factor.to_string(), *multiplicity))
+            .collect();
+        factors.sort();
+        factors
+    }
+
+    #[test]
+    fn splits_into_linear_factors() {
+        // Every element of GF(5) is a root of a^5 - a
+        let linear = factors::<5>("a^5 - a");
+        assert_eq!(linear.len(), 5);
+        assert!(linear.iter().all(|(_, multiplicity)| *multiplicity == 1));
+
+        assert_eq!(
+            factors::<3>("3a^4 + 2b^3 + b^4"),
+            vec![("1b".to_string(), 3), ("1b + 2".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn factors_powers_of_the_characteristic() {
+        assert_eq!(
+            factors::<2>("(a^3 + a + 1)^4 (a + 1)^3"),
+            vec![("1a + 1".to_string(), 3), ("1a^3 + 1a + 1".to_string(), 4)]
+        );
+        assert_eq!(
+            factors::<3>("(a^2 + 1)^6"),
+            vec![("1a^2 + 1".to_string(), 6)]
+        );
+        assert_eq!(factors::<7>("5").len(), 0);
+    }
+
+    #[test]
+    fn gcd_of_several_variables() {
+        let a: Polynomial<ModInt<3>> = "(a^2 + b)(ab + 2)^2".parse().unwrap();
+        let b: Polynomial<ModInt<3>> = "(ab + 2)(a - b)".parse().unwrap();
+        assert_eq!(a.gcd(&b), "ab + 2".parse().unwrap());
+
+        let a: Polynomial<ModInt<7>> = "3a^2c - 3b^2c".parse().unwrap();
+        let b: Polynomial<ModInt<7>> = "5ac + 5bc".parse().unwrap();
+        assert_eq!(a.gcd(&b), "ac + bc".parse().unwrap());
+    }
+
+    #[test]
+    fn computes_remainders_like_a_crc() {
+        // CRC-8 with generator a^8 + a^2 + a + 1 over the message a^7 + a shifted by 8 bits
+        let message: Polynomial<ModInt<2>> = "a^15 + a^9".parse().unwrap();
+        let generator: Polynomial<ModInt<2>> = "a^8 + a^2 + a + 1".parse().unwrap();
+        let (_, crc) = message.div_rem(&generator, Lex);
+
+        let codeword = message + crc;
+        assert!(codeword.div_rem(&generator, Lex).1.is_zero());
+    }
+}
@@ -0,0 +1,342 @@
+use super::{dense, Polynomial};
+use crate::{
+    order::Lex,
+    ring::{ExactDiv, Ring},
+    Gcd, Monomial, OverflowError,
+};
+use alloc::vec::Vec;
+use core::{iter, mem};
+
+/// Collect any polynomial into combined terms in canonical order
+fn canonical<C, T, const N: usize>(poly: &Polynomial<C, N, T>) -> Polynomial<C, N>
+where
+    C: ExactDiv,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    poly.monomials()
+        .iter()
+        .cloned()
+        .collect::<Polynomial<C, N>>()
+        .into_canonical()
+}
+
+/// Divide out the unit part of the leading coefficient under [`Lex`], which makes it
+/// positive over the integers and one over a field
+fn normalize<C, const N: usize>(poly: Polynomial<C, N>) -> Result<Polynomial<C, N>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    let poly = poly.into_canonical();
+    let unit = match poly.monomials().first() {
+        Some(lead) => lead
+            .coefficient
+            .exact_div(&lead.coefficient.gcd(&lead.coefficient))
+            .ok_or(OverflowError::Coefficient)?,
+        None => return Ok(poly),
+    };
+    if unit == C::one() {
+        return Ok(poly);
+    }
+    poly.monomials()
+        .iter()
+        .map(|monomial| {
+            let coefficient = monomial
+                .coefficient
+                .exact_div(&unit)
+                .ok_or(OverflowError::Coefficient)?;
+            Ok(Monomial::new(coefficient, monomial.exponents))
+        })
+        .collect()
+}
+
+fn degree_in<C, const N: usize>(poly: &Polynomial<C, N>, var: usize) -> Option<u16>
+where
+    C: ExactDiv,
+{
+    poly.nonzero().map(|monomial| monomial.exponents[var]).max()
+}
+
+/// The coefficient of `var^degree`, as a polynomial in the remaining variables
+fn coefficient_in<C, const N: usize>(
+    poly: &Polynomial<C, N>,
+    var: usize,
+    degree: u16,
+) -> Polynomial<C, N>
+where
+    C: ExactDiv,
+{
+    poly.nonzero()
+        .filter(|monomial| monomial.exponents[var] == degree)
+        .map(|monomial| monomial.exponent(var, 0))
+        .collect()
+}
+
+/// The greatest common divisor of the coefficients of `poly` viewed as a polynomial in `var`
+fn content_in<C, const N: usize>(
+    poly: &Polynomial<C, N>,
+    var: usize,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    let mut degrees: Vec<_> = poly
+        .nonzero()
+        .map(|monomial| monomial.exponents[var])
+        .collect();
+    degrees.sort_unstable();
+    degrees.dedup();
+
+    degrees
+        .into_iter()
+        .try_fold(Polynomial::zero(), |acc, degree| {
+            gcd(&acc, &coefficient_in(poly, var, degree))
+        })
+}
+
+fn exact_div<C, const N: usize>(
+    poly: &Polynomial<C, N>,
+    divisor: &Polynomial<C, N>,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    C: ExactDiv,
+{
+    let (quotient, remainder) = poly.checked_div_rem(divisor, Lex)?;
+    debug_assert!(remainder.is_zero());
+    Ok(quotient)
+}
+
+/// Pseudo-remainder of `a` divided by `b` as polynomials in `var`,
+/// scaling `a` by the leading coefficient of `b` instead of dividing
+fn pseudo_rem<C, const N: usize>(
+    a: &Polynomial<C, N>,
+    b: &Polynomial<C, N>,
+    var: usize,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    C: ExactDiv,
+{
+    let n = degree_in(b, var).unwrap_or(0);
+    let lead = coefficient_in(b, var, n);
+
+    let mut rem = a.clone();
+    while let Some(m) = degree_in(&rem, var).filter(|&m| m >= n) {
+        let shift = iter::once(Monomial::coefficient(C::one()).exponent(var, m - n))
+            .collect::<Polynomial<C, N>>();
+        let scaled = coefficient_in(&rem, var, m)
+            .checked_mul(b)?
+            .checked_mul(&shift)?;
+        rem = lead.checked_mul(&rem)?.checked_sub(&scaled)?;
+    }
+    Ok(rem)
+}
+
+/// Greatest common divisor by recursion on the variables,
+/// using a primitive polynomial remainder sequence in the first variable present
+fn gcd<C, const N: usize>(
+    a: &Polynomial<C, N>,
+    b: &Polynomial<C, N>,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    C: ExactDiv + Gcd<Output = C>,
+{
+    if a.is_zero() {
+        return normalize(b.clone());
+    }
+    if b.is_zero() {
+        return normalize(a.clone());
+    }
+
+    let var = (0..N)
+        .find(|&var| degree_in(a, var).unwrap_or(0) > 0 || degree_in(b, var).unwrap_or(0) > 0);
+    let var = match var {
+        Some(var) => var,
+        None => {
+            // Both are nonzero constants
+            let gcd = a.gcf().gcd(b.gcf());
+            return Ok(iter::once(gcd).collect());
+        }
+    };
+
+    let content_a = content_in(a, var)?;
+    let content_b = content_in(b, var)?;
+    let content = gcd(&content_a, &content_b)?;
+
+    let mut a = exact_div(a, &content_a)?;
+    let mut b = exact_div(b, &content_b)?;
+
+    let is_univariate = |poly: &Polynomial<C, N>| {
+        poly.nonzero()
+            .all(|monomial| (0..N).all(|v| v == var || monomial.exponents[v] == 0))
+    };
+    if is_univariate(&a) && is_univariate(&b) {
+        if let Some(g) = modular(&a, &b, var)? {
+            return normalize(content.checked_mul(&g)?);
+        }
+    }
+
+    if degree_in(&a, var) < degree_in(&b, var) {
+        mem::swap(&mut a, &mut b);
+    }
+
+    while !b.is_zero() {
+        let rem = pseudo_rem(&a, &b, var)?;
+        a = b;
+        b = if rem.is_zero() {
+            rem
+        } else {
+            exact_div(&rem, &content_in(&rem, var)?)?
+        };
+    }
+
+    normalize(content.checked_mul(&a)?)
+}
+
+/// Univariate remainder sequences over the integers quickly overflow, so work modulo primes
+/// instead when the coefficients are integers fitting in an `i128`, returning `None` otherwise
+fn modular<C, const N: usize>(
+    a: &Polynomial<C, N>,
+    b: &Polynomial<C, N>,
+    var: usize,
+) -> Result<Option<Polynomial<C, N>>, OverflowError>
+where
+    C: Ring,
+{
+    let g = match (dense::to_integers(a, var), dense::to_integers(b, var)) {
+        (Some(a), Some(b)) => dense::gcd_modular(&a, &b),
+        _ => None,
+    };
+    g.map(|g| dense::from_integers(&g, var).ok_or(OverflowError::Coefficient))
+        .transpose()
+}
+
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Compute the greatest common divisor like [`Gcd::gcd`],
+    /// returning an error instead of overflowing
+    /// ```
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let a: Polynomial<i64, 2> = "a^2b - b".parse().unwrap();
+    /// let b: Polynomial<i64, 2> = "ab^2 + b^2".parse().unwrap();
+    /// assert_eq!(a.checked_gcd(&b), Ok("ab + b".parse().unwrap()));
+    ///
+    /// let a: Polynomial<i64, 2> = "3037000500a^2b + 3037000499ab + 1".parse().unwrap();
+    /// let b: Polynomial<i64, 2> = "3037000501ab^2 + 7a + b".parse().unwrap();
+    /// assert_eq!(a.checked_gcd(&b), Err(OverflowError::Coefficient));
+    /// ```
+    ///
+    /// # Errors
+    /// If a coefficient overflows while eliminating a variable
+    pub fn checked_gcd<U>(
+        &self,
+        rhs: &Polynomial<C, N, U>,
+    ) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        gcd(&canonical(self), &canonical(rhs))
+    }
+}
+
+impl<C, T, U, const N: usize> Gcd<Polynomial<C, N, U>> for Polynomial<C, N, T>
+where
+    C: ExactDiv + Gcd<Output = C>,
+    T: AsRef<[Monomial<C, N>]>,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    type Output = Polynomial<C, N>;
+
+    /// Compute the greatest common divisor of two polynomials, normalized to a leading
+    /// coefficient under [`Lex`] that is positive over the integers and one over a field
+    /// ```
+    /// use embedded_algebra::{Gcd, ModInt, Polynomial};
+    ///
+    /// let a = Polynomial::from("a^2 - 1");
+    /// let b = Polynomial::from("2a^2 - 4a + 2");
+    ///
+    /// assert_eq!(a.gcd(&b), Polynomial::from("a - 1"));
+    ///
+    /// let a = Polynomial::from("(a + b)(a - 2c)^2");
+    /// let b = Polynomial::from("(2a + 2b)(a - 2c)(b + 1)");
+    ///
+    /// assert_eq!(a.gcd(&b), Polynomial::from("(a + b)(a - 2c)").into_canonical());
+    ///
+    /// let a: Polynomial<ModInt<5>> = "a^2 + 4".parse().unwrap();
+    /// let b: Polynomial<ModInt<5>> = "2a^2 + 3a + 1".parse().unwrap();
+    ///
+    /// assert_eq!(a.gcd(&b), "a + 1".parse().unwrap());
+    /// ```
+    ///
+    /// # Panics
+    /// If a coefficient overflows, see [`Polynomial::checked_gcd`]
+    fn gcd(&self, rhs: &Polynomial<C, N, U>) -> Self::Output {
+        self.checked_gcd(rhs).expect("coefficient overflows")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fraction, Gcd, Polynomial};
+
+    #[test]
+    fn gcd_with_zero() {
+        let a = Polynomial::from("-2a + 4");
+        let zero = Polynomial::from("0");
+
+        assert_eq!(a.gcd(&zero), Polynomial::from("2a - 4"));
+        assert_eq!(zero.gcd(&zero), zero);
+    }
+
+    #[test]
+    fn gcd_of_coprime() {
+        let a = Polynomial::from("a^2 + 1");
+        let b = Polynomial::from("a + 1");
+
+        assert_eq!(a.gcd(&b), Polynomial::from("1"));
+    }
+
+    #[test]
+    fn gcd_of_high_degree() {
+        let a = Polynomial::from("(3a^4 - a + 5)(a^6 + 7a^5 - 2a + 9)^2");
+        let b = Polynomial::from("(a^6 + 7a^5 - 2a + 9)(5a^7 - 4a^3 + 1)");
+
+        assert_eq!(
+            a.gcd(&b),
+            Polynomial::from("a^6 + 7a^5 - 2a + 9").into_canonical()
+        );
+    }
+
+    #[test]
+    fn gcd_of_integers() {
+        let a = Polynomial::from("6a + 12");
+        let b = Polynomial::from("4");
+
+        assert_eq!(a.gcd(&b), Polynomial::from("2"));
+    }
+
+    #[test]
+    fn simplifies_fraction() {
+        let frac = Fraction::new(Polynomial::from("a^2 - 1"), Polynomial::from("a - 1"));
+
+        assert_eq!(
+            frac.into_simplified(),
+            Fraction::new(Polynomial::from("a + 1"), Polynomial::from("1"))
+        );
+    }
+
+    #[test]
+    fn simplifies_multivariate_fraction() {
+        let frac = Fraction::new(
+            Polynomial::from("a^2b - b^3"),
+            Polynomial::from("ab + b^2 + a + b"),
+        );
+
+        assert_eq!(
+            frac.into_simplified(),
+            Fraction::new(Polynomial::from("ab - b^2"), Polynomial::from("b + 1"))
+        );
+    }
+}
@@ -1,63 +1,161 @@
-use crate::{parse::Parser, Fraction, Gcd, Monomial};
-use std::{
+#[cfg(feature = "alloc")]
+use crate::{parse, Fraction};
+use crate::{
+    ring::{ExactDiv, Ring},
+    Gcd, Monomial, Zero,
+};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::{cmp::Reverse, iter::FromIterator, ops::Div, str::FromStr};
+use core::{
     fmt,
-    iter::FromIterator,
-    ops::{Div, DivAssign, MulAssign},
-    str::FromStr,
+    marker::PhantomData,
+    ops::{DivAssign, Mul, MulAssign},
 };
 
+#[cfg(feature = "alloc")]
+mod add;
+
+mod arithmetic;
+
+mod array;
+pub use array::{Array, ArrayError, ArrayPolynomial};
+
+#[cfg(feature = "alloc")]
 mod builder;
+#[cfg(feature = "alloc")]
 pub use builder::Builder;
 
+mod calculus;
+
+#[cfg(feature = "alloc")]
+mod checked;
+
 mod combine;
 pub use combine::Combine;
 
-#[derive(Debug)]
-pub struct Polynomial<T = Box<[Monomial]>> {
+#[cfg(feature = "alloc")]
+mod dense;
+
+#[cfg(feature = "alloc")]
+mod div;
+
+mod eval;
+
+#[cfg(feature = "alloc")]
+mod factor;
+#[cfg(feature = "alloc")]
+pub use factor::Factorization;
+
+#[cfg(feature = "alloc")]
+mod finite;
+#[cfg(feature = "alloc")]
+pub use finite::ModFactorization;
+
+#[cfg(feature = "alloc")]
+mod gcd;
+
+#[cfg(feature = "alloc")]
+mod modp;
+
+mod normal;
+
+#[cfg(feature = "alloc")]
+mod partial;
+#[cfg(feature = "alloc")]
+pub use partial::{PartialFraction, PartialFractions};
+
+#[cfg(feature = "alloc")]
+mod resultant;
+
+mod subst;
+
+/// A sum of monomials stored in `T`, which defaults to a boxed slice with the `alloc` feature.
+///
+/// The storage used to be the only parameter, but a default may not depend on a parameter
+/// declared after it, so it now comes after the coefficient and number of variables.
+/// Code naming the storage of an `i64` polynomial in four variables, as in the old
+/// `Polynomial<Vec<Monomial>>`, can use [`IntPolynomial`] instead.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct Polynomial<C = i64, const N: usize = 4, T = Box<[Monomial<C, N>]>> {
     monomials: T,
+    _coefficient: PhantomData<C>,
 }
 
-impl Polynomial {
+/// A sum of monomials stored in `T`, which defaults to a boxed slice with the `alloc` feature
+#[cfg(not(feature = "alloc"))]
+#[derive(Clone, Debug)]
+pub struct Polynomial<C, const N: usize, T> {
+    monomials: T,
+    _coefficient: PhantomData<C>,
+}
+
+/// An `i64` polynomial in four variables stored in `T`, which is what `Polynomial<T>` meant
+/// before polynomials were generic over their coefficients
+/// ```
+/// use embedded_algebra::{poly::IntPolynomial, Monomial};
+///
+/// let poly: IntPolynomial<Vec<Monomial>> = IntPolynomial::new(vec![Monomial::from("2a")]);
+/// assert_eq!(poly.to_string(), "2a");
+/// ```
+pub type IntPolynomial<T> = Polynomial<i64, 4, T>;
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> Polynomial<C, N> {
     #[inline]
-    pub fn builder() -> Builder {
+    pub fn builder() -> Builder<C, N> {
         Builder::default()
     }
+
+    /// The polynomial with no terms
+    #[inline]
+    pub fn zero() -> Self {
+        Self::new(Box::new([]))
+    }
 }
 
-impl<T> Polynomial<T> {
+impl<C, const N: usize, T> Polynomial<C, N, T> {
     pub fn new(monomials: T) -> Self {
-        Self { monomials }
+        Self {
+            monomials,
+            _coefficient: PhantomData,
+        }
     }
 
-    pub fn monomials(&self) -> &[Monomial]
+    pub fn monomials(&self) -> &[Monomial<C, N>]
     where
-        T: AsRef<[Monomial]>,
+        T: AsRef<[Monomial<C, N>]>,
     {
         self.monomials.as_ref()
     }
 
-    pub fn monomials_mut(&mut self) -> &mut [Monomial]
+    pub fn monomials_mut(&mut self) -> &mut [Monomial<C, N>]
     where
-        T: AsMut<[Monomial]>,
+        T: AsMut<[Monomial<C, N>]>,
     {
         self.monomials.as_mut()
     }
 
     /// Compute the greatest common divisor as a monomial
     /// ```
+    /// # #[cfg(feature = "alloc")] {
     /// use embedded_algebra::{Monomial, Polynomial};
     ///
     /// let poly = Polynomial::from("6a^2 + 4a");
     /// let gcf = poly.gcf();
     ///
     /// assert_eq!(gcf, Monomial::from("2a"));
+    /// # }
     /// ```
     #[inline]
-    pub fn gcf(&self) -> Monomial
+    pub fn gcf(&self) -> Monomial<C, N>
     where
-        T: AsRef<[Monomial]>,
+        C: Ring + Gcd<Output = C>,
+        T: AsRef<[Monomial<C, N>]>,
     {
-        let mut iter = self.monomials().iter().copied();
+        let mut iter = self.monomials().iter().cloned();
         if let Some(init) = iter.next() {
             iter.fold(init, |acc, monomial| acc.gcd(monomial))
         } else {
@@ -65,112 +163,228 @@ impl<T> Polynomial<T> {
         }
     }
 
-    pub fn nonzero(&self) -> impl Iterator<Item = Monomial> + '_
+    /// Returns `true` if every coefficient is zero
+    pub fn is_zero(&self) -> bool
     where
-        T: AsRef<[Monomial]>,
+        C: Ring,
+        T: AsRef<[Monomial<C, N>]>,
+    {
+        self.nonzero().next().is_none()
+    }
+
+    pub fn nonzero(&self) -> impl Iterator<Item = Monomial<C, N>> + '_
+    where
+        C: Ring,
+        T: AsRef<[Monomial<C, N>]>,
     {
         self.monomials()
             .iter()
-            .copied()
-            .filter(|monomial| monomial.coefficient > 0)
+            .filter(|monomial| !monomial.coefficient.is_zero())
+            .cloned()
     }
 
     /// Returns an iterator that outputs combined terms
-    pub fn combine(self) -> Combine<T>
+    pub fn combine(self) -> Combine<C, N, T>
     where
-        T: AsMut<[Monomial]>,
+        C: Ring,
+        T: AsMut<[Monomial<C, N>]>,
     {
         self.into()
     }
 
     /// Combine like terms into a new polynomial
     /// ```
-    /// use embedded_algebra::Polynomial;
+    /// use embedded_algebra::{Monomial, Polynomial};
     ///
-    /// let poly = Polynomial::from("2a + 2a^2 + a");
+    /// let poly = Polynomial::builder()
+    ///     .monomial("2a")
+    ///     .monomial("2a^2")
+    ///     .monomial("a")
+    ///     .build();
     /// let combined = poly.into_combined();
     ///
-    /// assert_eq!(combined, Polynomial::from("3a + 2a^2"));
+    /// assert_eq!(
+    ///     combined.monomials(),
+    ///     &[Monomial::from("3a"), Monomial::from("2a^2")]
+    /// );
     /// ```
-    pub fn into_combined(self) -> Polynomial
+    #[cfg(feature = "alloc")]
+    pub fn into_combined(self) -> Polynomial<C, N>
     where
-        T: AsMut<[Monomial]>,
+        C: Ring,
+        T: AsMut<[Monomial<C, N>]>,
     {
         let monomials = self.combine().collect::<Vec<_>>().into();
         Polynomial::new(monomials)
     }
+
+    /// Combine like terms, drop zero terms and order the result by descending exponents
+    /// so that equal polynomials compare equal
+    /// ```
+    /// use embedded_algebra::{Monomial, Polynomial};
+    ///
+    /// let terms = ["b", "2a", "a^2", "a", "-b"];
+    /// let poly: Polynomial = terms.iter().map(|&term| Monomial::from(term)).collect();
+    /// let canonical = poly.into_canonical();
+    ///
+    /// assert_eq!(
+    ///     canonical.monomials(),
+    ///     &[Monomial::from("a^2"), Monomial::from("3a")]
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_canonical(self) -> Polynomial<C, N>
+    where
+        C: Ring,
+        T: AsMut<[Monomial<C, N>]>,
+    {
+        let mut monomials = self.combine().collect::<Vec<_>>();
+        monomials.sort_by_key(|monomial| Reverse(monomial.exponents));
+        Polynomial::new(monomials.into())
+    }
 }
 
-impl<T, U> Gcd<Polynomial<U>> for Polynomial<T>
+impl<C, const N: usize, T> Gcd<Monomial<C, N>> for Polynomial<C, N, T>
 where
-    T: AsRef<[Monomial]>,
-    U: AsRef<[Monomial]>,
+    C: Ring + Gcd<Output = C>,
+    T: AsRef<[Monomial<C, N>]>,
 {
-    type Output = Monomial;
+    type Output = Monomial<C, N>;
 
-    fn gcd(&self, rhs: &Polynomial<U>) -> Self::Output {
-        self.gcd(&rhs.gcf())
+    fn gcd(&self, rhs: &Monomial<C, N>) -> Self::Output {
+        self.gcf().gcd(rhs.clone())
     }
 }
 
-impl<T> Gcd<Monomial> for Polynomial<T>
+impl<C, const N: usize, T> Zero for Polynomial<C, N, T>
 where
-    T: AsRef<[Monomial]>,
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
 {
-    type Output = Monomial;
-
-    fn gcd(&self, rhs: &Monomial) -> Self::Output {
-        self.gcf().gcd(*rhs)
+    fn is_zero(&self) -> bool {
+        Polynomial::is_zero(self)
     }
 }
 
-impl<T> PartialEq for Polynomial<T>
+impl<C, const N: usize, T> DivAssign<Monomial<C, N>> for Polynomial<C, N, T>
 where
-    T: AsRef<[Monomial]>,
+    C: ExactDiv,
+    T: AsMut<[Monomial<C, N>]>,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.monomials() == other.monomials()
+    /// # Panics
+    /// If `rhs` does not divide every term exactly, see [`Polynomial::div_rem`]
+    fn div_assign(&mut self, rhs: Monomial<C, N>) {
+        for term in self.monomials_mut().iter_mut() {
+            *term /= rhs.clone();
+        }
     }
 }
 
-impl<T> DivAssign<Monomial> for Polynomial<T>
+#[cfg(feature = "alloc")]
+impl Div for Polynomial {
+    type Output = Fraction<Self, Self>;
+
+    /// Divide two polynomials into a fraction reduced to lowest terms
+    /// ```
+    /// use embedded_algebra::{Fraction, Polynomial};
+    ///
+    /// let frac = Polynomial::from("a^2 - 1") / Polynomial::from("2a - 2");
+    ///
+    /// assert_eq!(
+    ///     frac,
+    ///     Fraction::new(Polynomial::from("a + 1"), Polynomial::from("2"))
+    /// );
+    /// ```
+    fn div(self, rhs: Self) -> Self::Output {
+        Fraction::new(self, rhs).into_simplified()
+    }
+}
+
+impl<C, const N: usize, T> MulAssign<Monomial<C, N>> for Polynomial<C, N, T>
 where
-    T: AsMut<[Monomial]>,
+    C: Ring,
+    T: AsMut<[Monomial<C, N>]>,
 {
-    fn div_assign(&mut self, rhs: Monomial) {
-        for term in self.monomials_mut().iter_mut() {
-            *term /= rhs;
+    fn mul_assign(&mut self, rhs: Monomial<C, N>) {
+        for monomial in self.monomials_mut().iter_mut() {
+            *monomial *= rhs.clone();
         }
     }
 }
 
-impl<T, U> Div<Polynomial<U>> for Polynomial<T>
+impl<C, const N: usize, T> Mul<Monomial<C, N>> for Polynomial<C, N, T>
 where
-    T: AsRef<[Monomial]> + AsMut<[Monomial]>,
-    U: AsRef<[Monomial]> + AsMut<[Monomial]>,
+    C: Ring,
+    T: AsMut<[Monomial<C, N>]>,
 {
-    type Output = Fraction<Self, Polynomial<U>>;
+    type Output = Self;
 
-    fn div(self, rhs: Polynomial<U>) -> Self::Output {
-        Fraction::new(self, rhs).into_simplified()
+    fn mul(mut self, rhs: Monomial<C, N>) -> Self::Output {
+        self *= rhs;
+        self
     }
 }
 
-impl<T> MulAssign for Polynomial<T>
+#[cfg(feature = "alloc")]
+impl<C, const N: usize, T, U> Mul<&Polynomial<C, N, U>> for &Polynomial<C, N, T>
 where
-    T: AsRef<[Monomial]> + AsMut<[Monomial]>,
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+    U: AsRef<[Monomial<C, N>]>,
 {
-    fn mul_assign(&mut self, rhs: Self) {
-        let product: Monomial = rhs.monomials().iter().copied().product();
-        for monomial in self.monomials_mut().iter_mut() {
-            *monomial *= product;
-        }
+    type Output = Polynomial<C, N>;
+
+    /// Multiply two polynomials by distributing every term of `self` over `rhs`
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let a = Polynomial::from("a + 1");
+    /// let b = Polynomial::from("a + 1");
+    ///
+    /// assert_eq!(&a * &b, Polynomial::from("a^2 + 2a + 1"));
+    /// ```
+    fn mul(self, rhs: &Polynomial<C, N, U>) -> Self::Output {
+        self.monomials()
+            .iter()
+            .flat_map(|lhs| {
+                rhs.monomials()
+                    .iter()
+                    .map(move |rhs| lhs.clone() * rhs.clone())
+            })
+            .collect::<Polynomial<C, N>>()
+            .into_canonical()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize, T, U> Mul<Polynomial<C, N, U>> for Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    type Output = Polynomial<C, N>;
+
+    fn mul(self, rhs: Polynomial<C, N, U>) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize, U> MulAssign<Polynomial<C, N, U>> for Polynomial<C, N>
+where
+    C: Ring,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    fn mul_assign(&mut self, rhs: Polynomial<C, N, U>) {
+        *self = &*self * &rhs;
     }
 }
 
-impl<T> fmt::Display for Polynomial<T>
+impl<C, const N: usize, T> fmt::Display for Polynomial<C, N, T>
 where
-    T: AsRef<[Monomial]>,
+    C: Ring + fmt::Display,
+    T: AsRef<[Monomial<C, N>]>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut iter = self.monomials().iter();
@@ -178,27 +392,57 @@ where
             write!(f, "{}", first)?;
 
             for monomial in iter {
-                write!(f, " + {}", monomial)?;
+                if monomial.coefficient.is_negative() {
+                    write!(f, " - {}", -monomial.clone())?;
+                } else {
+                    write!(f, " + {}", monomial)?;
+                }
             }
+        } else {
+            f.write_str("0")?;
         }
         Ok(())
     }
 }
 
-impl FromIterator<Monomial> for Polynomial {
-    fn from_iter<T: IntoIterator<Item = Monomial>>(iter: T) -> Self {
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> FromIterator<Monomial<C, N>> for Polynomial<C, N> {
+    fn from_iter<T: IntoIterator<Item = Monomial<C, N>>>(iter: T) -> Self {
         Self::new(iter.into_iter().collect::<Vec<_>>().into())
     }
 }
 
-impl FromStr for Polynomial {
-    type Err = ();
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> FromStr for Polynomial<C, N>
+where
+    C: Ring,
+{
+    type Err = parse::Error;
 
+    /// Parse and [expand](parse::expand) an expression, reporting where the input is malformed
+    /// ```
+    /// use embedded_algebra::{
+    ///     parse::{Expected, Item, Operator, Span},
+    ///     ParseError, Polynomial,
+    /// };
+    ///
+    /// let error = "a++b".parse::<Polynomial>().unwrap_err();
+    ///
+    /// assert_eq!(error.span, Span::new(2, 3));
+    /// assert_eq!(
+    ///     error.kind,
+    ///     ParseError::Unexpected {
+    ///         expected: Expected::Term,
+    ///         found: Some(Item::Operator(Operator::Add)),
+    ///     }
+    /// );
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Parser::new(s).collect())
+        parse::expand(s)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<&str> for Polynomial {
     fn from(s: &str) -> Self {
         s.parse().unwrap()
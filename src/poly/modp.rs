@@ -0,0 +1,299 @@
+//! Dense univariate polynomials over the integers modulo a prime `p`,
+//! stored as coefficients from the constant term up with no trailing zeros
+
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+pub(crate) type Poly = Vec<u64>;
+
+fn trim(mut a: Poly) -> Poly {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+
+fn add_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((u128::from(a) + u128::from(b)) % u128::from(p)) as u64
+}
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((u128::from(a) * u128::from(b)) % u128::from(p)) as u64
+}
+
+pub(crate) fn degree(a: &[u64]) -> Option<usize> {
+    a.len().checked_sub(1)
+}
+
+pub(crate) fn from_ints(f: &[i128], p: u64) -> Poly {
+    let p = i128::from(p);
+    trim(f.iter().map(|&c| c.rem_euclid(p) as u64).collect())
+}
+
+pub(crate) fn pow(mut base: u64, mut exp: u64, p: u64) -> u64 {
+    let mut acc = 1 % p;
+    base %= p;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, base, p);
+        }
+        base = mul_mod(base, base, p);
+        exp >>= 1;
+    }
+    acc
+}
+
+/// Multiplicative inverse of a nonzero residue by Fermat's little theorem
+pub(crate) fn inv(a: u64, p: u64) -> u64 {
+    pow(a, p - 2, p)
+}
+
+pub(crate) fn add(a: &[u64], b: &[u64], p: u64) -> Poly {
+    let len = a.len().max(b.len());
+    trim(
+        (0..len)
+            .map(|i| add_mod(*a.get(i).unwrap_or(&0), *b.get(i).unwrap_or(&0), p))
+            .collect(),
+    )
+}
+
+pub(crate) fn sub(a: &[u64], b: &[u64], p: u64) -> Poly {
+    let len = a.len().max(b.len());
+    trim(
+        (0..len)
+            .map(|i| add_mod(*a.get(i).unwrap_or(&0), p - b.get(i).unwrap_or(&0), p))
+            .collect(),
+    )
+}
+
+pub(crate) fn scale(a: &[u64], c: u64, p: u64) -> Poly {
+    trim(a.iter().map(|&x| mul_mod(x, c, p)).collect())
+}
+
+pub(crate) fn mul(a: &[u64], b: &[u64], p: u64) -> Poly {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut out = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] = add_mod(out[i + j], mul_mod(x, y, p), p);
+        }
+    }
+    trim(out)
+}
+
+/// Quotient and remainder of `a` divided by a nonzero `b`
+pub(crate) fn div_rem(a: &[u64], b: &[u64], p: u64) -> (Poly, Poly) {
+    let db = degree(b).expect("attempt to divide by a zero polynomial");
+    let lead_inv = inv(b[db], p);
+
+    let mut rem = a.to_vec();
+    let mut quot = vec![0; a.len().saturating_sub(db)];
+    while let Some(dr) = degree(&rem).filter(|&dr| dr >= db) {
+        let c = mul_mod(rem[dr], lead_inv, p);
+        quot[dr - db] = c;
+        for (i, &y) in b.iter().enumerate() {
+            let x = &mut rem[dr - db + i];
+            *x = add_mod(*x, p - mul_mod(c, y, p), p);
+        }
+        rem = trim(rem);
+    }
+    (trim(quot), rem)
+}
+
+pub(crate) fn rem(a: &[u64], b: &[u64], p: u64) -> Poly {
+    div_rem(a, b, p).1
+}
+
+pub(crate) fn monic(a: &[u64], p: u64) -> Poly {
+    match a.last() {
+        Some(&lead) => scale(a, inv(lead, p), p),
+        None => Vec::new(),
+    }
+}
+
+pub(crate) fn gcd(a: &[u64], b: &[u64], p: u64) -> Poly {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    while !b.is_empty() {
+        let r = rem(&a, &b, p);
+        a = b;
+        b = r;
+    }
+    monic(&a, p)
+}
+
+/// Extended Euclidean algorithm returning the monic `g` and `s`, `t` with `s a + t b = g`
+pub(crate) fn ext_gcd(a: &[u64], b: &[u64], p: u64) -> (Poly, Poly, Poly) {
+    let (mut r0, mut r1) = (a.to_vec(), b.to_vec());
+    let (mut s0, mut s1) = (vec![1], Vec::new());
+    let (mut t0, mut t1) = (Vec::new(), vec![1]);
+
+    while !r1.is_empty() {
+        let (q, r) = div_rem(&r0, &r1, p);
+        let s = sub(&s0, &mul(&q, &s1, p), p);
+        let t = sub(&t0, &mul(&q, &t1, p), p);
+        r0 = mem::replace(&mut r1, r);
+        s0 = mem::replace(&mut s1, s);
+        t0 = mem::replace(&mut t1, t);
+    }
+
+    let lead_inv = inv(*r0.last().unwrap_or(&1), p);
+    (
+        scale(&r0, lead_inv, p),
+        scale(&s0, lead_inv, p),
+        scale(&t0, lead_inv, p),
+    )
+}
+
+pub(crate) fn pow_mod(base: &[u64], mut exp: u64, modulus: &[u64], p: u64) -> Poly {
+    let mut acc = rem(&[1], modulus, p);
+    let mut base = rem(base, modulus, p);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = rem(&mul(&acc, &base, p), modulus, p);
+        }
+        base = rem(&mul(&base, &base, p), modulus, p);
+        exp >>= 1;
+    }
+    acc
+}
+
+pub(crate) fn derivative(a: &[u64], p: u64) -> Poly {
+    trim(
+        a.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| mul_mod(i as u64 % p, c, p))
+            .collect(),
+    )
+}
+
+/// Split a monic square-free polynomial into products of irreducible factors sharing a degree
+pub(crate) fn distinct_degree(f: &[u64], p: u64) -> Vec<(Poly, usize)> {
+    let x = vec![0, 1];
+    let mut out = Vec::new();
+    let mut rest = f.to_vec();
+    let mut h = x.clone();
+    let mut d = 0;
+
+    while degree(&rest).is_some_and(|deg| deg >= 2 * (d + 1)) {
+        d += 1;
+        h = pow_mod(&h, p, &rest, p);
+        let g = gcd(&sub(&h, &x, p), &rest, p);
+        if degree(&g) != Some(0) {
+            rest = div_rem(&rest, &g, p).0;
+            h = rem(&h, &rest, p);
+            out.push((g, d));
+        }
+    }
+    if let Some(deg) = degree(&rest).filter(|&deg| deg > 0) {
+        out.push((rest, deg));
+    }
+    out
+}
+
+/// A small deterministic generator for the random splitting polynomials
+pub(crate) struct XorShift(pub u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Split a monic product of irreducible factors of degree `d` (Cantor-Zassenhaus)
+pub(crate) fn equal_degree(f: &[u64], d: usize, p: u64, rng: &mut XorShift) -> Vec<Poly> {
+    let n = degree(f).unwrap_or(0);
+    if n <= d {
+        return vec![f.to_vec()];
+    }
+
+    loop {
+        let a = trim((0..n).map(|_| rng.next() % p).collect());
+        if degree(&a).is_none_or(|deg| deg == 0) {
+            continue;
+        }
+
+        let g = if p == 2 {
+            // The trace a + a^2 + ... + a^(2^(d - 1)) takes values in GF(2) modulo each factor
+            let mut power = a.clone();
+            let mut trace = a;
+            for _ in 1..d {
+                power = rem(&mul(&power, &power, p), f, p);
+                trace = add(&trace, &power, p);
+            }
+            gcd(&trace, f, p)
+        } else {
+            // a^((p^d - 1) / 2) = (a^(1 + p + ... + p^(d - 1)))^((p - 1) / 2)
+            let mut frobenius = a.clone();
+            let mut norm = a;
+            for _ in 1..d {
+                frobenius = pow_mod(&frobenius, p, f, p);
+                norm = rem(&mul(&norm, &frobenius, p), f, p);
+            }
+            let b = pow_mod(&norm, (p - 1) / 2, f, p);
+            gcd(&sub(&b, &[1], p), f, p)
+        };
+        if degree(&g).is_some_and(|deg| deg > 0 && deg < n) {
+            let h = div_rem(f, &g, p).0;
+            let mut out = equal_degree(&g, d, p, rng);
+            out.extend(equal_degree(&monic(&h, p), d, p, rng));
+            return out;
+        }
+    }
+}
+
+/// Factor a monic square-free polynomial into monic irreducibles
+pub(crate) fn factor_square_free(f: &[u64], p: u64) -> Vec<Poly> {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    distinct_degree(f, p)
+        .into_iter()
+        .flat_map(|(g, d)| equal_degree(&g, d, p, &mut rng))
+        .collect()
+}
+
+/// Square-free decomposition of a monic polynomial into monic factors and their multiplicities
+pub(crate) fn square_free(f: &[u64], p: u64) -> Vec<(Poly, usize)> {
+    let is_constant = |a: &[u64]| degree(a).is_none_or(|deg| deg == 0);
+
+    let mut out = Vec::new();
+    let mut c = gcd(f, &derivative(f, p), p);
+    let mut w = div_rem(f, &c, p).0;
+    let mut multiplicity = 1;
+    while !is_constant(&w) {
+        let y = gcd(&w, &c, p);
+        let factor = div_rem(&w, &y, p).0;
+        if !is_constant(&factor) {
+            out.push((factor, multiplicity));
+        }
+        c = div_rem(&c, &y, p).0;
+        w = y;
+        multiplicity += 1;
+    }
+
+    // What remains is a polynomial in x^p, which is the p-th power of the same coefficients in x
+    if !is_constant(&c) {
+        let root: Poly = c.iter().step_by(p as usize).copied().collect();
+        for (factor, multiplicity) in square_free(&root, p) {
+            out.push((factor, multiplicity * p as usize));
+        }
+    }
+    out
+}
+
+/// Factor a monic polynomial into monic irreducibles and their multiplicities
+pub(crate) fn factor(f: &[u64], p: u64) -> Vec<(Poly, usize)> {
+    square_free(f, p)
+        .into_iter()
+        .flat_map(|(part, multiplicity)| {
+            factor_square_free(&part, p)
+                .into_iter()
+                .map(move |factor| (factor, multiplicity))
+        })
+        .collect()
+}
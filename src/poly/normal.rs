@@ -0,0 +1,222 @@
+//! The normal form of a polynomial, with like terms combined, zero terms dropped
+//! and the rest in descending [`Lex`](crate::order::Lex) order
+
+use super::Polynomial;
+use crate::{order::MonomialOrder, ring::Ring, Monomial};
+use core::{
+    hash::{Hash, Hasher},
+    iter,
+};
+
+/// Returns `true` if `terms` are already in normal form
+fn is_normal<C: Ring, const N: usize>(terms: &[Monomial<C, N>]) -> bool {
+    terms
+        .windows(2)
+        .all(|pair| pair[0].exponents > pair[1].exponents)
+        && terms.iter().all(|term| !term.coefficient.is_zero())
+}
+
+/// Iterate over the normal form of `terms` without allocating, by repeatedly
+/// finding and combining the next greatest exponents
+fn normal<C, const N: usize>(terms: &[Monomial<C, N>]) -> impl Iterator<Item = Monomial<C, N>> + '_
+where
+    C: Ring,
+{
+    let mut last: Option<[u16; N]> = None;
+    iter::from_fn(move || loop {
+        let exponents = terms
+            .iter()
+            .map(|term| term.exponents)
+            .filter(|exponents| last.is_none_or(|last| *exponents < last))
+            .max()?;
+        last = Some(exponents);
+
+        let coefficient = terms
+            .iter()
+            .filter(|term| term.exponents == exponents)
+            .fold(C::zero(), |acc, term| acc + term.coefficient.clone());
+        if !coefficient.is_zero() {
+            break Some(Monomial::new(coefficient, exponents));
+        }
+    })
+}
+
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Iterate over the terms of the normal form without allocating,
+    /// see [`Polynomial::into_canonical`]
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{Monomial, Polynomial};
+    ///
+    /// let poly: Polynomial = ["b", "a", "-b", "2a"].iter().map(|&s| Monomial::from(s)).collect();
+    ///
+    /// assert!(poly.normal().eq([Monomial::from("3a")]));
+    /// # }
+    /// ```
+    pub fn normal(&self) -> impl Iterator<Item = Monomial<C, N>> + '_ {
+        normal(self.monomials())
+    }
+
+    /// The greatest term of the normal form under `order`
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{
+    ///     order::{GrLex, Lex},
+    ///     Monomial, Polynomial,
+    /// };
+    ///
+    /// let poly = Polynomial::from("3a + 2b^2 - 1");
+    ///
+    /// assert_eq!(poly.leading_term(Lex), Some(Monomial::from("3a")));
+    /// assert_eq!(poly.leading_term(GrLex), Some(Monomial::from("2b^2")));
+    /// assert_eq!(Polynomial::<i64>::zero().leading_term(Lex), None);
+    /// # }
+    /// ```
+    pub fn leading_term<O>(&self, order: O) -> Option<Monomial<C, N>>
+    where
+        O: MonomialOrder,
+    {
+        self.normal()
+            .max_by(|lhs, rhs| order.compare(&lhs.exponents, &rhs.exponents))
+    }
+
+    /// The coefficient of the greatest term of the normal form under `order`
+    pub fn leading_coefficient<O>(&self, order: O) -> Option<C>
+    where
+        O: MonomialOrder,
+    {
+        self.leading_term(order).map(|term| term.coefficient)
+    }
+
+    /// The greatest exponent of the variable `var`, or `None` for the zero polynomial
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("a^3b + ab^2 - a^3b");
+    ///
+    /// assert_eq!(poly.degree(0), Some(1));
+    /// assert_eq!(poly.degree(1), Some(2));
+    /// assert_eq!(poly.total_degree(), Some(3));
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// If `var` is not less than `N`
+    pub fn degree(&self, var: usize) -> Option<u16> {
+        assert!(var < N, "variable out of range");
+        if is_normal(self.monomials()) {
+            self.monomials()
+                .iter()
+                .map(|term| term.exponents[var])
+                .max()
+        } else {
+            self.normal().map(|term| term.exponents[var]).max()
+        }
+    }
+
+    /// The greatest sum of exponents of any term, or `None` for the zero polynomial
+    pub fn total_degree(&self) -> Option<u32> {
+        let degree = |term: &Monomial<C, N>| term.exponents.iter().map(|&exp| u32::from(exp)).sum();
+        if is_normal(self.monomials()) {
+            self.monomials().iter().map(degree).max()
+        } else {
+            self.normal().map(|term| degree(&term)).max()
+        }
+    }
+
+    /// Sort terms in place by descending `order`, without combining them
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{order::GrevLex, Polynomial};
+    ///
+    /// let mut poly = Polynomial::from("a^2 + b^3 + ab^2c");
+    /// poly.sort_by_order(GrevLex);
+    ///
+    /// assert_eq!(poly.to_string(), "1ab^2c + 1b^3 + 1a^2");
+    /// # }
+    /// ```
+    pub fn sort_by_order<O>(&mut self, order: O)
+    where
+        O: MonomialOrder,
+        T: AsMut<[Monomial<C, N>]>,
+    {
+        self.monomials_mut()
+            .sort_unstable_by(|lhs, rhs| order.compare(&rhs.exponents, &lhs.exponents));
+    }
+}
+
+impl<C, const N: usize, T> PartialEq for Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Compare normal forms, so that term order and uncombined terms do not matter
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_algebra::{Monomial, Polynomial};
+    ///
+    /// let poly: Polynomial = ["b", "a", "0", "b"].iter().map(|&s| Monomial::from(s)).collect();
+    ///
+    /// assert_eq!(poly, Polynomial::from("a + 2b"));
+    /// # }
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        self.monomials() == other.monomials() || self.normal().eq(other.normal())
+    }
+}
+
+impl<C, const N: usize, T> Eq for Polynomial<C, N, T>
+where
+    C: Ring + Eq,
+    T: AsRef<[Monomial<C, N>]>,
+{
+}
+
+impl<C, const N: usize, T> Hash for Polynomial<C, N, T>
+where
+    C: Ring + Hash,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Hash the normal form, consistently with [`PartialEq`]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0usize;
+        for term in self.normal() {
+            term.hash(state);
+            len += 1;
+        }
+        len.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse::parse_into, Monomial, Polynomial};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash<T: AsRef<[Monomial]>>(poly: &Polynomial<i64, 4, T>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        poly.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn hashes_normal_form() {
+        let shuffled = ["c", "a^2", "-b", "c", "b", "-4"].map(Monomial::from);
+        let mut normal = [Monomial::default(); 3];
+        parse_into("a^2 + 2c - 4", &mut normal).unwrap();
+        let (shuffled, normal) = (Polynomial::new(&shuffled[..]), Polynomial::new(&normal[..]));
+
+        assert_eq!(shuffled, normal);
+        assert_eq!(hash(&shuffled), hash(&normal));
+        assert_ne!(
+            hash(&Polynomial::new([Monomial::from("a")])),
+            hash(&Polynomial::new([Monomial::from("b")]))
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Partial fraction decomposition of univariate rational functions
+
+use super::{
+    dense::{self, from_dense, to_dense},
+    Polynomial,
+};
+use crate::{
+    ring::{ExactDiv, Ring},
+    Fraction, Rational,
+};
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+
+/// Dense coefficients from the constant term up, with no trailing zeros
+type Poly = Vec<Rational<i128>>;
+
+fn trim(mut a: Poly) -> Poly {
+    while a.last() == Some(&Rational::zero()) {
+        a.pop();
+    }
+    a
+}
+
+fn from_ints(a: &[i128]) -> Poly {
+    a.iter().map(|&c| Rational::from(c)).collect()
+}
+
+fn sub(a: &[Rational<i128>], b: &[Rational<i128>]) -> Option<Poly> {
+    let mut out = vec![Rational::zero(); a.len().max(b.len())];
+    for (i, c) in out.iter_mut().enumerate() {
+        let x = a.get(i).copied().unwrap_or(Rational::zero());
+        let y = b.get(i).copied().unwrap_or(Rational::zero());
+        *c = x.checked_sub(&y)?;
+    }
+    Some(trim(out))
+}
+
+fn mul(a: &[Rational<i128>], b: &[Rational<i128>]) -> Option<Poly> {
+    if a.is_empty() || b.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut out = vec![Rational::zero(); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] = out[i + j].checked_add(&x.checked_mul(&y)?)?;
+        }
+    }
+    Some(trim(out))
+}
+
+/// # Panics
+/// If `b` is zero
+fn div_rem(a: &[Rational<i128>], b: &[Rational<i128>]) -> Option<(Poly, Poly)> {
+    let db = b.len() - 1;
+    let mut rem = a.to_vec();
+    if rem.len() < b.len() {
+        return Some((Vec::new(), rem));
+    }
+
+    let mut quot = vec![Rational::zero(); rem.len() - db];
+    for i in (0..quot.len()).rev() {
+        let c = rem[i + db].exact_div(&b[db])?;
+        quot[i] = c;
+        for (j, &y) in b.iter().enumerate() {
+            rem[i + j] = rem[i + j].checked_sub(&c.checked_mul(&y)?)?;
+        }
+    }
+    Some((trim(quot), trim(rem)))
+}
+
+/// The inverse of `a` modulo `m`, or `None` if they share a factor
+fn inv_mod(a: &[Rational<i128>], m: &[Rational<i128>]) -> Option<Poly> {
+    let (mut r0, mut r1) = (m.to_vec(), div_rem(a, m)?.1);
+    let (mut s0, mut s1) = (Vec::new(), vec![Rational::one()]);
+    while !r1.is_empty() {
+        let (q, r) = div_rem(&r0, &r1)?;
+        let s = sub(&s0, &mul(&q, &s1)?)?;
+        r0 = r1;
+        r1 = r;
+        s0 = s1;
+        s1 = s;
+    }
+
+    match r0[..] {
+        [c] => s0.iter().map(|x| x.exact_div(&c)).collect(),
+        _ => None,
+    }
+}
+
+/// Write `a / f^power` as a fraction of integer polynomials
+fn to_fraction<const N: usize>(
+    a: &[Rational<i128>],
+    f: &[Rational<i128>],
+    power: u16,
+    var: usize,
+) -> Option<Fraction<Polynomial<i64, N>, Polynomial<i64, N>>> {
+    let lcm = a.iter().try_fold(1i128, |lcm, c| {
+        lcm.checked_mul(c.denominator() / dense::gcd_int(lcm, *c.denominator()))
+    })?;
+    let mut denominator = vec![Rational::from(lcm)];
+    for _ in 0..power {
+        denominator = mul(&denominator, f)?;
+    }
+
+    let to_ints = |poly: &[Rational<i128>], scale: i128| {
+        poly.iter()
+            .map(|c| {
+                let c = c.numerator().checked_mul(&(scale / c.denominator()))?;
+                i64::try_from(c).ok().map(i128::from)
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+    let numerator = to_ints(a, lcm)?;
+    let denominator = to_ints(&denominator, 1)?;
+    Some(
+        Fraction::new(from_dense(&numerator, var), from_dense(&denominator, var)).into_simplified(),
+    )
+}
+
+/// A proper fraction over a power of an irreducible polynomial
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartialFraction<const N: usize = 4> {
+    /// The fraction itself, over a constant multiple of `factor^power`
+    pub fraction: Fraction<Polynomial<i64, N>, Polynomial<i64, N>>,
+    /// An irreducible factor of the decomposed denominator
+    pub factor: Polynomial<i64, N>,
+    pub power: u16,
+}
+
+/// A rational function written as a polynomial plus a sum of proper fractions
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartialFractions<const N: usize = 4> {
+    /// The polynomial part, over a constant denominator
+    pub polynomial: Fraction<Polynomial<i64, N>, Polynomial<i64, N>>,
+    pub terms: Vec<PartialFraction<N>>,
+}
+
+impl<const N: usize> Fraction<Polynomial<i64, N>, Polynomial<i64, N>> {
+    /// Decompose this into a polynomial plus fractions over powers of the irreducible
+    /// factors of the denominator, or return `None` if more than one variable appears,
+    /// the denominator is zero or a coefficient overflows
+    /// ```
+    /// use embedded_algebra::{Fraction, Polynomial};
+    ///
+    /// let frac = Fraction::new(Polynomial::from("a^3"), Polynomial::from("a^2 - 1"));
+    /// let decomposition = frac.partial_fractions().unwrap();
+    ///
+    /// assert_eq!(decomposition.polynomial, Fraction::new(Polynomial::from("a"), Polynomial::from("1")));
+    /// for term in decomposition.terms {
+    ///     assert_eq!(term.fraction.numerator, Polynomial::from("1"));
+    ///     assert_eq!(term.fraction.denominator, &Polynomial::from("2") * &term.factor);
+    /// }
+    /// ```
+    pub fn partial_fractions(&self) -> Option<PartialFractions<N>> {
+        let (numerator, denominator) = (&self.numerator, &self.denominator);
+        let mut vars = (0..N).filter(|&var| {
+            numerator
+                .normal()
+                .chain(denominator.normal())
+                .any(|monomial| monomial.exponents[var] > 0)
+        });
+        let var = vars.next().unwrap_or(0);
+        if vars.next().is_some() || denominator.is_zero() {
+            return None;
+        }
+
+        let factorization = denominator.factor().ok()?;
+        let q = from_ints(&to_dense(denominator, var));
+        let (s, r) = div_rem(&from_ints(&to_dense(numerator, var)), &q)?;
+        let polynomial = to_fraction(&s, &[], 0, var)?;
+
+        let mut terms = Vec::new();
+        for (factor, power) in factorization.factors {
+            let f = from_ints(&to_dense(&factor, var));
+            let mut g = vec![Rational::one()];
+            for _ in 0..power {
+                g = mul(&g, &f)?;
+            }
+
+            // The numerator `a` over `g` is `r / (q / g)` modulo `g`
+            let (h, _) = div_rem(&q, &g)?;
+            let (_, mut a) = div_rem(&mul(&r, &inv_mod(&h, &g)?)?, &g)?;
+
+            // Expand `a` in powers of `f`, giving numerators over decreasing powers of `f`
+            for k in (1..=power).rev() {
+                let (quot, rem) = div_rem(&a, &f)?;
+                if !rem.is_empty() {
+                    terms.push(PartialFraction {
+                        fraction: to_fraction(&rem, &f, k, var)?,
+                        factor: factor.clone(),
+                        power: k,
+                    });
+                }
+                a = quot;
+            }
+        }
+
+        Some(PartialFractions { polynomial, terms })
+    }
+}
+
+impl<const N: usize> From<PartialFraction<N>> for Fraction<Polynomial<i64, N>, Polynomial<i64, N>> {
+    fn from(term: PartialFraction<N>) -> Self {
+        term.fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fraction, Polynomial};
+
+    fn sum(s: &str, t: &str) -> Fraction<Polynomial, Polynomial> {
+        let frac = Fraction::new(Polynomial::from(s), Polynomial::from(t));
+        let decomposition = frac.partial_fractions().unwrap();
+        decomposition
+            .terms
+            .into_iter()
+            .fold(decomposition.polynomial, |acc, term| acc + term.fraction)
+    }
+
+    #[test]
+    fn recombines_to_the_original() {
+        for (s, t) in [
+            ("1", "a^2 - 1"),
+            ("3a^4 + 2a - 7", "(a - 2)^3 (a^2 + 1)^2"),
+            ("5b^2 + 1", "6b^3 - 2b^2"),
+            ("a^5", "2a^2 + 4"),
+        ]
+        .iter()
+        {
+            let frac = Fraction::new(Polynomial::from(*s), Polynomial::from(*t));
+            assert_eq!(sum(s, t), frac);
+        }
+    }
+
+    #[test]
+    fn keeps_proper_terms() {
+        let frac = Fraction::new(Polynomial::from("a^3 + 1"), Polynomial::from("(a^2 + 1)^2"));
+        let decomposition = frac.partial_fractions().unwrap();
+
+        assert!(decomposition.polynomial.numerator.is_zero());
+        assert_eq!(decomposition.terms.len(), 2);
+        for term in &decomposition.terms {
+            assert_eq!(term.factor, Polynomial::from("a^2 + 1"));
+            assert!(term.fraction.numerator.degree(0) < term.factor.degree(0));
+        }
+        assert!(Fraction::new(Polynomial::from("a"), Polynomial::from("b"))
+            .partial_fractions()
+            .is_none());
+    }
+}
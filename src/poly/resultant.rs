@@ -0,0 +1,146 @@
+//! Resultants, by fraction-free elimination of the Sylvester matrix
+
+use super::{
+    arithmetic::Checked,
+    checked::{canonical, combine},
+    Polynomial,
+};
+use crate::{order::Lex, ring::ExactDiv, Monomial, OverflowError};
+use alloc::{vec, vec::Vec};
+
+/// The coefficients of `poly` as a polynomial in `var`, from the highest degree down,
+/// after combining like terms so that cancelled terms do not count towards the degree
+fn coefficients<C, const N: usize, T>(
+    poly: &Polynomial<C, N, T>,
+    var: usize,
+) -> Result<Vec<Polynomial<C, N>>, OverflowError>
+where
+    C: ExactDiv,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    let poly = canonical(combine::<Checked, C, N>(poly.monomials().to_vec())?);
+    let degree = match poly
+        .monomials()
+        .iter()
+        .map(|monomial| monomial.exponents[var])
+        .max()
+    {
+        Some(degree) => degree,
+        None => return Ok(Vec::new()),
+    };
+    Ok((0..=degree)
+        .rev()
+        .map(|exp| {
+            poly.monomials()
+                .iter()
+                .filter(|monomial| monomial.exponents[var] == exp)
+                .map(|monomial| monomial.clone().exponent(var, 0))
+                .collect::<Polynomial<C, N>>()
+                .into_canonical()
+        })
+        .collect())
+}
+
+/// The determinant of a square matrix by Bareiss' algorithm, where every division is exact
+fn determinant<C, const N: usize>(
+    mut matrix: Vec<Vec<Polynomial<C, N>>>,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    C: ExactDiv,
+{
+    let n = matrix.len();
+    let mut previous = Polynomial::new(vec![Monomial::coefficient(C::one())].into());
+    let mut negate = false;
+
+    for p in 0..n {
+        if matrix[p][p].is_zero() {
+            match (p + 1..n).find(|&row| !matrix[row][p].is_zero()) {
+                Some(row) => {
+                    matrix.swap(p, row);
+                    negate = !negate;
+                }
+                None => return Ok(Polynomial::zero()),
+            }
+        }
+
+        for i in p + 1..n {
+            for j in p + 1..n {
+                let lhs = matrix[p][p].checked_mul(&matrix[i][j])?;
+                let rhs = matrix[i][p].checked_mul(&matrix[p][j])?;
+                let (quotient, remainder) =
+                    lhs.checked_sub(&rhs)?.checked_div_rem(&previous, Lex)?;
+                debug_assert!(remainder.is_zero());
+                matrix[i][j] = quotient;
+            }
+        }
+        previous = matrix[p][p].clone();
+    }
+
+    if negate {
+        previous.checked_neg()
+    } else {
+        Ok(previous)
+    }
+}
+
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: ExactDiv,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// The resultant of this and `other` as polynomials in the variable `var`,
+    /// which is a polynomial in the other variables that vanishes exactly where
+    /// they have a common root in `var`, or zero if either is zero.
+    /// Intermediate results are no larger than minors of the Sylvester matrix,
+    /// and with the `bigint` feature `BigInt` coefficients never overflow.
+    /// ```
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let circle = Polynomial::from("a^2 + b^2 - 1");
+    /// let line = Polynomial::from("a - b");
+    /// assert_eq!(circle.resultant(&line, 0), Ok(Polynomial::from("2b^2 - 1")));
+    ///
+    /// let a = Polynomial::from("a^2 - 1");
+    /// assert_eq!(a.resultant(&Polynomial::from("a - 2"), 0), Ok(Polynomial::from("3")));
+    /// assert_eq!(a.resultant(&Polynomial::from("a + 1"), 0), Ok(Polynomial::zero()));
+    ///
+    /// let large = Polynomial::from("4000000000a^3 - 1");
+    /// assert_eq!(large.resultant(&a, 0), Err(OverflowError::Coefficient));
+    ///
+    /// // The leading terms cancel, leaving a polynomial of degree one
+    /// let line: Polynomial = ["a^2", "a", "-2", "-a^2"].iter().map(|&s| s.into()).collect();
+    /// assert_eq!(a.resultant(&line, 0), Ok(Polynomial::from("3")));
+    /// ```
+    ///
+    /// # Errors
+    /// If a coefficient or exponent overflows
+    pub fn resultant<U>(
+        &self,
+        other: &Polynomial<C, N, U>,
+        var: usize,
+    ) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        let (a, b) = (coefficients(self, var)?, coefficients(other, var)?);
+        if a.is_empty() || b.is_empty() {
+            return Ok(Polynomial::zero());
+        }
+
+        // Shifted copies of the coefficients of `self`, one per degree of `other`, then of `other`
+        let (m, k) = (a.len() - 1, b.len() - 1);
+        let row = |coefficients: &[Polynomial<C, N>], shift: usize| {
+            (0..m + k)
+                .map(|col| match col.checked_sub(shift) {
+                    Some(idx) if idx < coefficients.len() => coefficients[idx].clone(),
+                    _ => Polynomial::zero(),
+                })
+                .collect()
+        };
+        let matrix = (0..k)
+            .map(|shift| row(&a, shift))
+            .chain((0..m).map(|shift| row(&b, shift)))
+            .collect();
+        determinant(matrix)
+    }
+}
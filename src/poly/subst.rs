@@ -0,0 +1,289 @@
+//! Substituting polynomials for variables
+
+#[cfg(feature = "alloc")]
+use super::{
+    arithmetic::{Arithmetic, Checked, Unchecked},
+    checked::{canonical, combine, product},
+    Polynomial,
+};
+use crate::Monomial;
+#[cfg(feature = "alloc")]
+use crate::{ring::Ring, OverflowError};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::{array, marker::PhantomData};
+
+impl<C, const N: usize> Monomial<C, N> {
+    /// Move each variable `var` to `map[var]`, adding exponents where variables are merged
+    /// ```
+    /// use embedded_algebra::Monomial;
+    ///
+    /// let mono = Monomial::from("2a^2bc^3");
+    ///
+    /// assert_eq!(mono.rename(&[1, 0, 2, 3]), Monomial::from("2ab^2c^3"));
+    /// assert_eq!(mono.rename(&[2, 1, 2, 3]), Monomial::from("2bc^5"));
+    /// ```
+    /// # Panics
+    /// If merged exponents overflow or a variable in `map` is not less than `N`
+    pub fn rename(mut self, map: &[usize; N]) -> Self {
+        let exponents = self.exponents;
+        self.exponents = [0; N];
+        for (&exp, &var) in exponents.iter().zip(map.iter()) {
+            let dest = &mut self.exponents[var];
+            *dest = dest.checked_add(exp).expect("exponent overflow");
+        }
+        self
+    }
+}
+
+/// The powers of a polynomial, computed as they are needed by repeated squaring
+#[cfg(feature = "alloc")]
+struct Powers<'a, A, C, const N: usize, U> {
+    base: &'a Polynomial<C, N, U>,
+    /// `base^(2^k)` at index `k`
+    squares: Vec<Polynomial<C, N>>,
+    /// The powers asked for so far
+    cache: Vec<(u16, Polynomial<C, N>)>,
+    arithmetic: PhantomData<A>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, A, C, const N: usize, U> Powers<'a, A, C, N, U>
+where
+    A: Arithmetic<C, N>,
+    C: Ring,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    fn new(base: &'a Polynomial<C, N, U>) -> Self {
+        Self {
+            base,
+            squares: Vec::new(),
+            cache: Vec::new(),
+            arithmetic: PhantomData,
+        }
+    }
+
+    fn square(&mut self, k: usize) -> Result<&Polynomial<C, N>, OverflowError> {
+        while self.squares.len() <= k {
+            let next = match self.squares.last() {
+                Some(last) => product::<A, C, N>(last.monomials(), last.monomials())?,
+                None => canonical(combine::<A, C, N>(self.base.monomials().to_vec())?),
+            };
+            self.squares.push(next);
+        }
+        Ok(&self.squares[k])
+    }
+
+    fn get(&mut self, exp: u16) -> Result<&Polynomial<C, N>, OverflowError> {
+        let pos = match self.cache.iter().position(|(cached, _)| *cached == exp) {
+            Some(pos) => pos,
+            None => {
+                let mut power = canonical(vec![Monomial::coefficient(C::one())]);
+                for k in (0..16).filter(|&k| exp & 1 << k != 0) {
+                    power = product::<A, C, N>(power.monomials(), self.square(k)?.monomials())?;
+                }
+                self.cache.push((exp, power));
+                self.cache.len() - 1
+            }
+        };
+        Ok(&self.cache[pos].1)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn substitute<A, C, const N: usize, U>(
+    terms: &[Monomial<C, N>],
+    var: usize,
+    value: &Polynomial<C, N, U>,
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    A: Arithmetic<C, N>,
+    C: Ring,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    let mut powers = Powers::<A, C, N, U>::new(value);
+    let mut out = Vec::new();
+    for monomial in terms {
+        let rest = monomial.clone().exponent(var, 0);
+        let power = powers.get(monomial.exponents[var])?;
+        out.extend(
+            product::<A, C, N>(power.monomials(), &[rest])?
+                .monomials
+                .into_vec(),
+        );
+    }
+    Ok(canonical(combine::<A, C, N>(out)?))
+}
+
+#[cfg(feature = "alloc")]
+fn compose<A, C, const N: usize, U>(
+    terms: &[Monomial<C, N>],
+    values: &[Polynomial<C, N, U>; N],
+) -> Result<Polynomial<C, N>, OverflowError>
+where
+    A: Arithmetic<C, N>,
+    C: Ring,
+    U: AsRef<[Monomial<C, N>]>,
+{
+    let mut powers: [_; N] = array::from_fn(|var| Powers::<A, C, N, U>::new(&values[var]));
+    let mut out = Vec::new();
+    for monomial in terms {
+        let mut term = canonical(vec![Monomial::coefficient(monomial.coefficient.clone())]);
+        for (powers, &exp) in powers.iter_mut().zip(monomial.exponents.iter()) {
+            if exp > 0 {
+                term = product::<A, C, N>(term.monomials(), powers.get(exp)?.monomials())?;
+            }
+        }
+        out.extend(term.monomials.into_vec());
+    }
+    Ok(canonical(combine::<A, C, N>(out)?))
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize, T> Polynomial<C, N, T>
+where
+    C: Ring,
+    T: AsRef<[Monomial<C, N>]>,
+{
+    /// Replace the variable `var` with `value`
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("a^2 + ab");
+    ///
+    /// assert_eq!(
+    ///     poly.substitute(0, &Polynomial::from("b + 1")),
+    ///     Polynomial::from("2b^2 + 3b + 1")
+    /// );
+    /// ```
+    /// # Panics
+    /// If a coefficient or exponent overflows and overflow checks are enabled,
+    /// as with the `*` operator, see [`Polynomial::checked_substitute`]
+    pub fn substitute<U>(&self, var: usize, value: &Polynomial<C, N, U>) -> Polynomial<C, N>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        // Unchecked arithmetic always succeeds
+        substitute::<Unchecked, C, N, U>(self.monomials(), var, value).unwrap()
+    }
+
+    /// Replace the variable `var` with `value`, returning an error instead of overflowing
+    /// ```
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let poly: Polynomial<i8> = "a^2 + ab".parse().unwrap();
+    /// let value = "b + 1".parse::<Polynomial<i8>>().unwrap();
+    /// assert_eq!(poly.checked_substitute(0, &value).unwrap().to_string(), "2b^2 + 3b + 1");
+    ///
+    /// let value = "b + 12".parse::<Polynomial<i8>>().unwrap();
+    /// assert_eq!(poly.checked_substitute(0, &value), Err(OverflowError::Coefficient));
+    /// ```
+    pub fn checked_substitute<U>(
+        &self,
+        var: usize,
+        value: &Polynomial<C, N, U>,
+    ) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        substitute::<Checked, C, N, U>(self.monomials(), var, value)
+    }
+
+    /// Replace every variable `var` at once with `values[var]`
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("a^2 - b");
+    /// let values = ["a + b", "ab", "c", "d"].map(Polynomial::from);
+    ///
+    /// assert_eq!(poly.compose(&values), Polynomial::from("a^2 + ab + b^2"));
+    /// ```
+    /// # Panics
+    /// If a coefficient or exponent overflows and overflow checks are enabled,
+    /// as with the `*` operator, see [`Polynomial::checked_compose`]
+    pub fn compose<U>(&self, values: &[Polynomial<C, N, U>; N]) -> Polynomial<C, N>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        compose::<Unchecked, C, N, U>(self.monomials(), values).unwrap()
+    }
+
+    /// Replace every variable `var` at once with `values[var]`,
+    /// returning an error instead of overflowing
+    /// ```
+    /// use embedded_algebra::{OverflowError, Polynomial};
+    ///
+    /// let poly = Polynomial::from("a^2 - b");
+    /// let values = ["a^40000", "b", "c", "d"].map(Polynomial::from);
+    /// assert_eq!(poly.checked_compose(&values), Err(OverflowError::Exponent));
+    /// ```
+    pub fn checked_compose<U>(
+        &self,
+        values: &[Polynomial<C, N, U>; N],
+    ) -> Result<Polynomial<C, N>, OverflowError>
+    where
+        U: AsRef<[Monomial<C, N>]>,
+    {
+        compose::<Checked, C, N, U>(self.monomials(), values)
+    }
+
+    /// Move each variable `var` to `map[var]`, adding exponents where variables are merged
+    /// ```
+    /// use embedded_algebra::Polynomial;
+    ///
+    /// let poly = Polynomial::from("a^2b + b - c");
+    ///
+    /// assert_eq!(poly.rename(&[1, 0, 2, 3]), Polynomial::from("ab^2 + a - c"));
+    /// assert_eq!(poly.rename(&[0, 0, 0, 3]), Polynomial::from("a^3"));
+    /// ```
+    /// # Panics
+    /// If merged exponents overflow or a variable in `map` is not less than `N`
+    pub fn rename(&self, map: &[usize; N]) -> Polynomial<C, N> {
+        assert!(map.iter().all(|&var| var < N), "variable out of range");
+        self.monomials()
+            .iter()
+            .map(|monomial| monomial.clone().rename(map))
+            .collect::<Polynomial<C, N>>()
+            .into_canonical()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::Polynomial;
+
+    #[test]
+    fn composes_like_substituting_in_turn() {
+        let poly = Polynomial::from("a^3b - 2ab^2 + b + 7");
+        let (a, b) = (Polynomial::from("b - 1"), Polynomial::from("c^2"));
+
+        // Substitute `b` first so the `b` introduced for `a` is left alone
+        let expected = poly.substitute(1, &b).substitute(0, &a);
+        let values = [a, b, Polynomial::from("c"), Polynomial::from("d")];
+
+        assert_eq!(poly.compose(&values), expected);
+        assert_eq!(Polynomial::zero().compose(&values), Polynomial::zero());
+    }
+
+    #[test]
+    fn substitutes_constants() {
+        let poly = Polynomial::from("a^2b + 3a - 4");
+        let value = poly.substitute(0, &Polynomial::from("2"));
+
+        assert_eq!(value, Polynomial::from("4b + 2"));
+        assert_eq!(
+            poly.substitute(0, &Polynomial::zero()),
+            Polynomial::from("-4")
+        );
+    }
+
+    #[test]
+    fn substitutes_into_high_powers() {
+        // Only the squares up to a^8192 are needed rather than every power below a^10000
+        let poly = Polynomial::from("a^10000 - a^3");
+        let value = poly.substitute(0, &Polynomial::from("-b"));
+
+        assert_eq!(value, Polynomial::from("b^10000 + b^3"));
+    }
+}
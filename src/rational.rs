@@ -0,0 +1,287 @@
+//! Exact rational numbers, for coefficients that need to be divided
+
+use crate::{
+    ring::{ExactDiv, Ring},
+    Gcd, Monomial, ParseError,
+};
+use core::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+/// A fraction of integers kept in lowest terms with a positive denominator,
+/// so integration and division stay exact
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use embedded_algebra::{Polynomial, Rational};
+///
+/// let poly: Polynomial<Rational> = "1/2a^2 + 0.25b".parse().unwrap();
+/// assert_eq!(poly.to_string(), "1/2a^2 + 1/4b");
+/// assert_eq!(poly.integral(0).unwrap().to_string(), "1/6a^3 + 1/4ab");
+///
+/// assert_eq!(Rational::new(4, -6), "-2/3".parse().unwrap());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational<T = i64> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T> Rational<T>
+where
+    T: ExactDiv + Gcd<Output = T>,
+{
+    /// # Panics
+    /// If `denominator` is zero or reducing overflows
+    pub fn new(numerator: T, denominator: T) -> Self {
+        Self::checked_new(numerator, denominator).expect("invalid rational")
+    }
+
+    /// Reduce `numerator / denominator` to lowest terms,
+    /// or return `None` if `denominator` is zero or reducing overflows
+    pub fn checked_new(numerator: T, denominator: T) -> Option<Self> {
+        if denominator.is_zero() {
+            return None;
+        }
+
+        let gcd = numerator.gcd(&denominator);
+        let gcd = if denominator.is_negative() {
+            gcd.checked_neg()?
+        } else {
+            gcd
+        };
+        Some(Self {
+            numerator: numerator.exact_div(&gcd)?,
+            denominator: denominator.exact_div(&gcd)?,
+        })
+    }
+
+    pub fn numerator(&self) -> &T {
+        &self.numerator
+    }
+
+    /// The denominator, which is always positive
+    pub fn denominator(&self) -> &T {
+        &self.denominator
+    }
+
+    /// The multiplicative inverse, or `None` for zero
+    pub fn recip(&self) -> Option<Self> {
+        Self::checked_new(self.denominator.clone(), self.numerator.clone())
+    }
+}
+
+impl<T> Ring for Rational<T>
+where
+    T: ExactDiv + Gcd<Output = T>,
+{
+    fn zero() -> Self {
+        T::zero().into()
+    }
+
+    fn one() -> Self {
+        T::one().into()
+    }
+
+    fn from_i64(n: i64) -> Option<Self> {
+        T::from_i64(n).map(Self::from)
+    }
+
+    fn from_ratio(numerator: i64, denominator: i64) -> Option<Self> {
+        Self::checked_new(T::from_i64(numerator)?, T::from_i64(denominator)?)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.numerator.is_negative()
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        // Scale by the cofactors of the denominators to keep intermediates small
+        let gcd = self.denominator.gcd(&rhs.denominator);
+        let lhs_scale = rhs.denominator.exact_div(&gcd)?;
+        let rhs_scale = self.denominator.exact_div(&gcd)?;
+        let numerator = self
+            .numerator
+            .checked_mul(&lhs_scale)?
+            .checked_add(&rhs.numerator.checked_mul(&rhs_scale)?)?;
+        Self::checked_new(numerator, self.denominator.checked_mul(&lhs_scale)?)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.checked_add(&rhs.checked_neg()?)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        // Cancel across the product first, so the result is already in lowest terms
+        let lhs_gcd = self.numerator.gcd(&rhs.denominator);
+        let rhs_gcd = rhs.numerator.gcd(&self.denominator);
+        let numerator = self
+            .numerator
+            .exact_div(&lhs_gcd)?
+            .checked_mul(&rhs.numerator.exact_div(&rhs_gcd)?)?;
+        let denominator = self
+            .denominator
+            .exact_div(&rhs_gcd)?
+            .checked_mul(&rhs.denominator.exact_div(&lhs_gcd)?)?;
+        Some(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator.clone(),
+        })
+    }
+}
+
+impl<T> ExactDiv for Rational<T>
+where
+    T: ExactDiv + Gcd<Output = T>,
+{
+    fn exact_div(&self, rhs: &Self) -> Option<Self> {
+        self.checked_mul(&rhs.recip()?)
+    }
+}
+
+impl<T> Gcd for Rational<T>
+where
+    T: ExactDiv + Gcd<Output = T>,
+{
+    type Output = Self;
+
+    /// The greatest common divisor of the numerators over the least common multiple
+    /// of the denominators, so that dividing by it leaves coprime integers
+    fn gcd(&self, rhs: &Self) -> Self::Output {
+        let gcd = self.denominator.gcd(&rhs.denominator);
+        let lcm = self.denominator.exact_div(&gcd).unwrap() * rhs.denominator.clone();
+        Self::new(self.numerator.gcd(&rhs.numerator), lcm)
+    }
+}
+
+macro_rules! impl_op {
+    ($($op:ident, $f:ident, $checked:ident);*) => {
+        $(
+            impl<T> $op for Rational<T>
+            where
+                T: ExactDiv + Gcd<Output = T>,
+            {
+                type Output = Self;
+
+                fn $f(self, rhs: Self) -> Self::Output {
+                    Ring::$checked(&self, &rhs).expect("rational overflow")
+                }
+            }
+        )*
+    };
+}
+
+impl_op!(Add, add, checked_add; Sub, sub, checked_sub; Mul, mul, checked_mul);
+
+impl<T> Neg for Rational<T>
+where
+    T: ExactDiv + Gcd<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("rational overflow")
+    }
+}
+
+impl<T: Ring> From<T> for Rational<T> {
+    fn from(numerator: T) -> Self {
+        Self {
+            numerator,
+            denominator: T::one(),
+        }
+    }
+}
+
+impl<T, const N: usize> From<Rational<T>> for Monomial<Rational<T>, N> {
+    fn from(coefficient: Rational<T>) -> Self {
+        Self::coefficient(coefficient)
+    }
+}
+
+impl<T> fmt::Display for Rational<T>
+where
+    T: Ring + fmt::Display,
+{
+    /// Write `numerator/denominator`, or just the numerator for integers
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == T::one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl<T> FromStr for Rational<T>
+where
+    T: ExactDiv + Gcd<Output = T>,
+{
+    type Err = ParseError;
+
+    /// Parse an integer, `numerator/denominator` or a decimal, with an optional minus sign
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Monomial<Self, 0>>()
+            .map(|monomial| monomial.coefficient)
+            .map_err(|error| error.kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rational;
+    use crate::{ring::Ring, Gcd, Monomial, ParseError};
+
+    #[test]
+    fn normalizes_signs_and_common_factors() {
+        assert_eq!(Rational::new(-4, -10), Rational::new(2, 5));
+        assert_eq!(Rational::new(3, -9).to_string(), "-1/3");
+        assert_eq!(Rational::new(0, -7), Rational::zero());
+        assert_eq!(Rational::checked_new(1, 0), None);
+        assert_eq!(Rational::<i8>::checked_new(1, i8::MIN), None);
+    }
+
+    #[test]
+    fn stays_in_lowest_terms() {
+        let (a, b) = (Rational::new(1, 6), Rational::new(3, 10));
+
+        assert_eq!(a + b, Rational::new(7, 15));
+        assert_eq!(a - b, Rational::new(-2, 15));
+        assert_eq!(a * b, Rational::new(1, 20));
+        assert_eq!(b.recip(), Some(Rational::new(10, 3)));
+        assert_eq!(a.gcd(&b), Rational::new(1, 30));
+        assert_eq!(
+            Rational::<i8>::new(1, 100).checked_add(&Rational::new(1, 99)),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_coefficients() {
+        assert_eq!("-0.125".parse(), Ok(Rational::new(-1, 8)));
+        assert_eq!("6/4".parse(), Ok(Rational::new(3, 2)));
+        assert_eq!("1/0".parse::<Rational>(), Err(ParseError::Coefficient));
+        assert_eq!(
+            "-1/2ab^2".parse(),
+            Ok(Monomial::new(Rational::new(-1, 2), [1, 2, 0, 0]))
+        );
+        assert_eq!(
+            "1/2a".parse::<Monomial>().map_err(|error| error.kind),
+            Err(ParseError::Coefficient)
+        );
+        assert_eq!("4/2a".parse(), Ok(Monomial::from("2a")));
+    }
+}
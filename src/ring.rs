@@ -0,0 +1,259 @@
+//! Coefficient types for monomials and polynomials
+use crate::{Gcd, Zero};
+use core::{
+    convert::TryFrom,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// A commutative ring with identity that coefficients are drawn from
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use embedded_algebra::{ring::Ring, Monomial, Polynomial};
+///
+/// assert_eq!(Ring::checked_mul(&i64::MAX, &2), None);
+///
+/// let poly = "a^2 - 2a".parse::<Polynomial<f64>>().unwrap() * Monomial::coefficient(0.5);
+/// assert_eq!(poly.to_string(), "0.5a^2 - 1a");
+/// # }
+/// ```
+pub trait Ring:
+    Clone
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    /// The additive identity
+    fn zero() -> Self;
+
+    /// The multiplicative identity
+    fn one() -> Self;
+
+    /// Convert an integer, or return `None` if it cannot be represented
+    fn from_i64(n: i64) -> Option<Self>;
+
+    /// Convert a ratio of integers, or return `None` if it cannot be represented,
+    /// which by default is unless the denominator divides the numerator
+    fn from_ratio(numerator: i64, denominator: i64) -> Option<Self> {
+        if numerator.checked_rem(denominator)? != 0 {
+            return None;
+        }
+        Self::from_i64(numerator.checked_div(denominator)?)
+    }
+
+    /// Convert a string of decimal digits, or return `None` if it cannot be represented,
+    /// which by default accumulates them with checked arithmetic
+    fn from_digits(digits: &str) -> Option<Self> {
+        let ten = Self::from_i64(10)?;
+        digits.bytes().try_fold(Self::zero(), |acc, digit| {
+            if !digit.is_ascii_digit() {
+                return None;
+            }
+            acc.checked_mul(&ten)?
+                .checked_add(&Self::from_i64(i64::from(digit - b'0'))?)
+        })
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    /// Returns `true` if this is written with a leading minus sign
+    fn is_negative(&self) -> bool {
+        false
+    }
+
+    /// The value of an integer that fits in an `i128`, which lets algorithms over the integers
+    /// work on machine words, or `None` for everything else
+    fn to_i128(&self) -> Option<i128> {
+        None
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self.clone() + rhs.clone())
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(self.clone() - rhs.clone())
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self.clone() * rhs.clone())
+    }
+
+    fn checked_neg(&self) -> Option<Self> {
+        Some(-self.clone())
+    }
+}
+
+impl<C: Ring> Zero for C {
+    fn is_zero(&self) -> bool {
+        Ring::is_zero(self)
+    }
+}
+
+/// Rings whose arithmetic clamps to the nearest representable value instead of overflowing.
+///
+/// The defaults are the plain operators, for rings that never overflow.
+/// [`Rational`](crate::Rational) over machine integers does not implement this,
+/// since there is no nearest value to clamp to when a denominator overflows.
+pub trait Saturating: Ring {
+    fn saturating_add(&self, rhs: &Self) -> Self {
+        self.clone() + rhs.clone()
+    }
+
+    fn saturating_sub(&self, rhs: &Self) -> Self {
+        self.clone() - rhs.clone()
+    }
+
+    fn saturating_mul(&self, rhs: &Self) -> Self {
+        self.clone() * rhs.clone()
+    }
+
+    fn saturating_neg(&self) -> Self {
+        -self.clone()
+    }
+}
+
+/// Rings where one element may be divided by another when the quotient exists
+pub trait ExactDiv: Ring {
+    /// Divide by `rhs`, returning `None` if it is zero or does not divide this exactly
+    fn exact_div(&self, rhs: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Ring for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn from_i64(n: i64) -> Option<Self> {
+                    <$t>::try_from(n).ok()
+                }
+
+                fn is_negative(&self) -> bool {
+                    *self < 0
+                }
+
+                fn to_i128(&self) -> Option<i128> {
+                    Some(i128::from(*self))
+                }
+
+                fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *rhs)
+                }
+
+                fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *rhs)
+                }
+
+                fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *rhs)
+                }
+
+                fn checked_neg(&self) -> Option<Self> {
+                    <$t>::checked_neg(*self)
+                }
+            }
+
+            impl Saturating for $t {
+                fn saturating_add(&self, rhs: &Self) -> Self {
+                    <$t>::saturating_add(*self, *rhs)
+                }
+
+                fn saturating_sub(&self, rhs: &Self) -> Self {
+                    <$t>::saturating_sub(*self, *rhs)
+                }
+
+                fn saturating_mul(&self, rhs: &Self) -> Self {
+                    <$t>::saturating_mul(*self, *rhs)
+                }
+
+                fn saturating_neg(&self) -> Self {
+                    <$t>::saturating_neg(*self)
+                }
+            }
+
+            impl ExactDiv for $t {
+                fn exact_div(&self, rhs: &Self) -> Option<Self> {
+                    if <$t>::checked_rem(*self, *rhs)? != 0 {
+                        return None;
+                    }
+                    <$t>::checked_div(*self, *rhs)
+                }
+            }
+
+            impl Gcd for $t {
+                type Output = Self;
+
+                /// The non-negative greatest common divisor, except that it wraps around to
+                /// `MIN` when it is `-MIN`, which only happens if both are `0` or `MIN`
+                fn gcd(&self, rhs: &Self) -> Self::Output {
+                    let (mut m, mut n) = (self.unsigned_abs(), rhs.unsigned_abs());
+                    while m != 0 {
+                        let old_m = m;
+                        m = n % m;
+                        n = old_m;
+                    }
+                    n as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(i8, i16, i32, i64, i128);
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {
+        $(
+            impl Ring for $t {
+                fn zero() -> Self {
+                    0.
+                }
+
+                fn one() -> Self {
+                    1.
+                }
+
+                fn from_i64(n: i64) -> Option<Self> {
+                    Some(n as $t)
+                }
+
+                fn from_ratio(numerator: i64, denominator: i64) -> Option<Self> {
+                    if denominator == 0 {
+                        None
+                    } else {
+                        Some(numerator as $t / denominator as $t)
+                    }
+                }
+
+                fn is_negative(&self) -> bool {
+                    *self < 0.
+                }
+            }
+
+            /// Overflow already rounds to an infinity
+            impl Saturating for $t {}
+
+            impl ExactDiv for $t {
+                fn exact_div(&self, rhs: &Self) -> Option<Self> {
+                    if *rhs == 0. {
+                        None
+                    } else {
+                        Some(self / rhs)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);